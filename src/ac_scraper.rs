@@ -8,59 +8,137 @@ use std::{
 
 use anyhow::{anyhow, Context, Result};
 use colored::*;
+use regex::Regex;
 use reqwest::{
     header::{HeaderMap, HeaderValue, COOKIE},
     Response,
 };
 use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
 use shellexpand::full;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
 
 use crate::{
-    config::{ProblemInfo, ProblemStrInfo},
+    cache::{load, load_or_fetch, samples_cache_path, tasks_cache_path},
+    config::{ConfigStrMap, ProblemInfo, ProblemStrInfo},
+    custom_cases::TestCase,
     data::ACN,
+    statement::{parse_statements, StatementSection},
     util::str_format,
 };
 
 const PARSE_ERROR: &str = "Parse error occurred in getting samples.";
-const INPUT_HEADER: &str = "入力例";
-const OUTPUT_HEADER: &str = "出力例";
-const TASKS_URL: &str = "https://atcoder.jp/contests/{{contest_type}}{{contest_id_0_pad}}/tasks";
-const PROBLEM_URL: &str = "https://atcoder.jp/contests/{{contest_type}}{{contest_id_0_pad}}/tasks/{{task_screen_name}}?lang=ja";
+const DEFAULT_LANGUAGE: &str = "ja";
+// (言語, 入力例の見出し, 出力例の見出し)
+const SAMPLE_HEADERS: [(&str, &str, &str); 2] = [
+    ("ja", "入力例", "出力例"),
+    ("en", "Sample Input", "Sample Output"),
+];
+const CONTEST_URL: &str = "https://atcoder.jp/contests/{{contest}}";
+const TASKS_URL: &str = "https://atcoder.jp/contests/{{contest}}/tasks";
+const PROBLEM_URL: &str =
+    "https://atcoder.jp/contests/{{contest}}/tasks/{{task_screen_name}}?lang={{language}}";
+const HOME_URL: &str = "https://atcoder.jp/home";
 const LOGIN_URL: &str = "https://atcoder.jp/login";
 const LOCAL_SESSION_PATH: &str = "~/.ac-ninja/session.txt";
 const LOCAL_DIR: &str = "~/.ac-ninja";
 
+// (サンプルの番号, 中身)
+type SampleCases = Vec<(usize, String)>;
+
+#[derive(Default, Serialize, Deserialize)]
 pub struct Samples {
-    pub inputs: Vec<(usize, String)>,
-    pub outputs: Vec<(usize, String)>,
+    pub inputs: SampleCases,
+    pub outputs: SampleCases,
+    #[serde(skip)]
     pub size: usize,
+    pub time_limit_ms: Option<u64>,
+    pub memory_limit_mb: Option<u64>,
+    pub score: Option<u64>,
+    pub eps: Option<f64>,
+    pub is_interactive: bool,
+    pub statements: HashMap<String, Vec<StatementSection>>,
+    #[serde(skip)]
+    pub custom_cases: Vec<TestCase>,
 }
 
-fn problem_id_to_index(id: &str) -> Result<usize> {
-    match id {
-        "a" => Ok(0),
-        "b" => Ok(1),
-        "c" => Ok(2),
-        "d" => Ok(3),
-        "e" => Ok(4),
-        "f" => Ok(5),
-        "g" => Ok(6),
-        "h" => Ok(7),
-        "ex" => Ok(7),
-        _ => Err(anyhow!("Failed to convert problem_id to problem index")),
-    }
+fn parse_time_limit_ms(body: &str) -> Option<u64> {
+    let re = Regex::new(r"(?:実行時間制限|Time Limit)\s*:\s*([0-9.]+)\s*sec").unwrap();
+    let caps = re.captures(body)?;
+    let sec: f64 = caps[1].parse().ok()?;
+    Some((sec * 1000.0).round() as u64)
 }
 
-pub async fn add_task_name_to_problem_info(
+fn parse_memory_limit_mb(body: &str) -> Option<u64> {
+    let re = Regex::new(r"(?:メモリ制限|Memory Limit)\s*:\s*([0-9]+)\s*Mi?B").unwrap();
+    let caps = re.captures(body)?;
+    caps[1].parse().ok()
+}
+
+fn parse_score(body: &str) -> Option<u64> {
+    let re = Regex::new(r"(?:配点|Score)\s*:\s*(?:<var>)?\s*([0-9]+)").unwrap();
+    let caps = re.captures(body)?;
+    caps[1].parse().ok()
+}
+
+fn is_interactive_problem(body: &str) -> bool {
+    let re = Regex::new(r"(?i)インタラクティブ|interactive (?:task|problem)").unwrap();
+    re.is_match(body)
+}
+
+// "絶対誤差または相対誤差が 10^{-6} 以下" のような記述から許容誤差を読み取る
+fn parse_eps(body: &str) -> Option<f64> {
+    let re = Regex::new(
+        r"(?s)(?:誤差|absolute error|relative error).{0,200}?10\s*\^\s*\{?\s*-\s*(\d+)\s*\}?",
+    )
+    .unwrap();
+    let caps = re.captures(body)?;
+    let exp: i32 = caps[1].parse().ok()?;
+    Some(10f64.powi(-exp))
+}
+
+// コンテストのトップページのスクリプトにある開始時刻を読み取る
+fn parse_start_time(body: &str) -> Option<OffsetDateTime> {
+    let re = Regex::new(r#"startTime\s*=\s*moment\(\s*"([^"]+)"\s*\)"#).unwrap();
+    let caps = re.captures(body)?;
+    OffsetDateTime::parse(&caps[1], &Rfc3339).ok()
+}
+
+pub async fn get_contest_start_time(
     acn: &ACN,
-    mut problem_info: ProblemInfo,
-    mut problem_str_info: ProblemStrInfo,
-) -> Result<(ProblemInfo, ProblemStrInfo)> {
-    let tasks_url = str_format(TASKS_URL.to_string(), &problem_str_info);
+    problem_str_info: &ProblemStrInfo,
+) -> Result<OffsetDateTime> {
+    let contest_url = str_format(CONTEST_URL.to_string(), problem_str_info);
     let cookies = load_cookie_headers()?;
     let resp = acn
         .client
-        .get(tasks_url.clone())
+        .get(contest_url.clone())
+        .headers(cookies)
+        .send()
+        .await?
+        .error_for_status()?;
+    save_cookie(&resp).await?;
+    let body = resp.text().await?;
+    parse_start_time(&body)
+        .with_context(|| format!("Couldn't find the start time in {}", contest_url))
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Task {
+    pub label: String,
+    pub task_screen_name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TaskList {
+    tasks: Vec<Task>,
+}
+
+async fn fetch_task_list(acn: &ACN, tasks_url: &str) -> Result<TaskList> {
+    let cookies = load_cookie_headers()?;
+    let resp = acn
+        .client
+        .get(tasks_url)
         .headers(cookies)
         .send()
         .await?
@@ -71,9 +149,9 @@ pub async fn add_task_name_to_problem_info(
 
     let selctor = Selector::parse("table tbody tr td:nth-child(1)").unwrap();
     let tds = doc.select(&selctor);
-    let config_id = problem_str_info.get("problem_id").unwrap();
+    let mut tasks: Vec<Task> = Vec::new();
     for td in tds {
-        let id = td
+        let label = td
             .first_child()
             .unwrap()
             .first_child()
@@ -81,7 +159,8 @@ pub async fn add_task_name_to_problem_info(
             .value()
             .as_text()
             .unwrap()
-            .to_lowercase();
+            .trim()
+            .to_string();
         let href = td
             .first_child()
             .unwrap()
@@ -90,20 +169,118 @@ pub async fn add_task_name_to_problem_info(
             .unwrap()
             .attr("href")
             .unwrap();
-        let now_idx = problem_id_to_index(&id)?;
-        let config_idx = problem_id_to_index(config_id)?;
-        if now_idx == config_idx {
-            let task_screen_name: String = href.split('/').next_back().unwrap().to_string();
-            problem_info.task_screen_name = task_screen_name.clone();
-            problem_str_info.insert("task_screen_name".to_string(), task_screen_name);
-            return Ok((problem_info, problem_str_info));
+        let task_screen_name: String = href.split('/').next_back().unwrap().to_string();
+        tasks.push(Task {
+            label,
+            task_screen_name,
+        });
+    }
+
+    // 開始前のコンテストの空の一覧はキャッシュしない
+    if tasks.is_empty() {
+        return Err(anyhow!("No tasks found in {}", tasks_url));
+    }
+
+    Ok(TaskList { tasks })
+}
+
+pub fn get_tasks_url(problem_str_info: &ProblemStrInfo) -> String {
+    str_format(TASKS_URL.to_string(), problem_str_info)
+}
+
+pub async fn get_task_list(acn: &ACN, problem_str_info: &ProblemStrInfo) -> Result<Vec<Task>> {
+    let tasks_url = get_tasks_url(problem_str_info);
+    let task_list = load_or_fetch(
+        acn.cache_mode,
+        &tasks_cache_path(problem_str_info)?,
+        fetch_task_list(acn, &tasks_url),
+    )
+    .await?;
+    Ok(task_list.tasks)
+}
+
+// ネットワークにはアクセスせず, キャッシュにある問題一覧だけを読む
+pub fn get_cached_task_list(problem_str_info: &ProblemStrInfo) -> Option<Vec<Task>> {
+    let task_list: TaskList = load(&tasks_cache_path(problem_str_info).ok()?)?;
+    Some(task_list.tasks)
+}
+
+// 問題ページから読み取った制限と配点を加える. テンプレートでも使えるようにする
+pub fn add_limits_to_problem_info(
+    samples: &Samples,
+    mut problem_info: ProblemInfo,
+    mut problem_str_info: ProblemStrInfo,
+) -> (ProblemInfo, ProblemStrInfo) {
+    problem_info.time_limit_ms = samples.time_limit_ms;
+    problem_info.memory_limit_mb = samples.memory_limit_mb;
+    problem_info.score = samples.score;
+    for (key, value) in [
+        ("time_limit_ms", samples.time_limit_ms),
+        ("memory_limit_mb", samples.memory_limit_mb),
+        ("score", samples.score),
+    ] {
+        if let Some(value) = value {
+            problem_str_info.insert(key.to_string(), value.to_string());
         }
     }
+    (problem_info, problem_str_info)
+}
+
+// ABC233〜318の8問目はExなので, hとexは見つからなければ互いに読み替える
+const LABEL_ALIASES: [(&str, &str); 2] = [("h", "ex"), ("ex", "h")];
 
+// 問題一覧の見出し(A, Ex, A1, 001など)と大文字小文字を区別せずに比べる
+fn find_task<'a>(tasks: &'a [Task], problem_id: &str) -> Option<&'a Task> {
+    let find = |label: &str| {
+        tasks
+            .iter()
+            .find(|task| task.label.eq_ignore_ascii_case(label))
+    };
+    find(problem_id).or_else(|| {
+        LABEL_ALIASES
+            .iter()
+            .find(|(from, _)| from.eq_ignore_ascii_case(problem_id))
+            .and_then(|(_, to)| find(to))
+    })
+}
+
+pub fn find_task_by_screen_name<'a>(tasks: &'a [Task], task_screen_name: &str) -> Option<&'a Task> {
+    tasks
+        .iter()
+        .find(|task| task.task_screen_name == task_screen_name)
+}
+
+// hで指定してもExに解決されたらprepareと同じex.cppを使えるよう, problem_idを見出しに揃える
+fn set_task(task: &Task, problem_info: &mut ProblemInfo, problem_str_info: &mut ProblemStrInfo) {
+    problem_info.problem_id = task.label.to_lowercase();
+    problem_info.task_screen_name = task.task_screen_name.clone();
+    problem_str_info.insert("problem_id".to_string(), problem_info.problem_id.clone());
+    problem_str_info.insert(
+        "task_screen_name".to_string(),
+        task.task_screen_name.clone(),
+    );
+}
+
+pub async fn add_task_name_to_problem_info(
+    acn: &ACN,
+    mut problem_info: ProblemInfo,
+    mut problem_str_info: ProblemStrInfo,
+) -> Result<(ProblemInfo, ProblemStrInfo)> {
+    let tasks_url = get_tasks_url(&problem_str_info);
+    let tasks = get_task_list(acn, &problem_str_info).await?;
+
+    let config_id = problem_str_info.get("problem_id").unwrap();
+    if let Some(task) = find_task(&tasks, config_id) {
+        set_task(task, &mut problem_info, &mut problem_str_info);
+        return Ok((problem_info, problem_str_info));
+    }
+
+    let labels: Vec<&str> = tasks.iter().map(|task| task.label.as_str()).collect();
     Err(anyhow!(
-        "Couldn't find {} problem in {}",
+        "Couldn't find {} problem in {} (available: {})",
         config_id.to_uppercase(),
-        tasks_url
+        tasks_url,
+        labels.join(", ")
     ))
 }
 
@@ -257,6 +434,39 @@ pub async fn ac_login(acn: &ACN) -> Result<()> {
     Ok(())
 }
 
+// 保存したセッションでページを取得する. ログインページに飛ばされた場合はエラーにする
+pub async fn get_with_session(acn: &ACN, url: &str) -> Result<Response> {
+    let cookies = get_local_session()?
+        .context("Local session not found. Please run `ac-ninja login` first.")?;
+    let resp = acn
+        .client
+        .get(url)
+        .headers(cookies)
+        .send()
+        .await?
+        .error_for_status()?;
+    save_cookie(&resp).await?;
+    if resp.url().path() == "/login" {
+        return Err(anyhow!(
+            "Your session has expired. Please run `ac-ninja login` again."
+        ));
+    }
+    Ok(resp)
+}
+
+// ログイン中のユーザー名をページのスクリプトから読み取る
+fn parse_user_screen_name(body: &str) -> Option<String> {
+    let re = Regex::new(r#"userScreenName\s*=\s*"([^"]+)""#).unwrap();
+    let caps = re.captures(body)?;
+    Some(caps[1].to_string())
+}
+
+pub async fn get_user_screen_name(acn: &ACN) -> Result<String> {
+    let body = get_with_session(acn, HOME_URL).await?.text().await?;
+    parse_user_screen_name(&body)
+        .context("Couldn't find your username. Please check you logged in.")
+}
+
 pub async fn ac_check_login(acn: &ACN) -> Result<bool> {
     let cookies = load_cookie_headers()?;
     if cookies.is_empty() {
@@ -264,7 +474,7 @@ pub async fn ac_check_login(acn: &ACN) -> Result<bool> {
     }
     let resp = acn
         .client
-        .get("https://atcoder.jp/home")
+        .get(HOME_URL)
         .headers(cookies)
         .send()
         .await?
@@ -315,12 +525,55 @@ pub fn get_local_session() -> Result<Option<HeaderMap>> {
     Ok(Some(cookie_headers))
 }
 
-pub async fn get_sample_cases(
-    problem_str_info: &ProblemStrInfo,
-    acn: &ACN,
-    sample_case_id_arg: Option<usize>,
-) -> Result<Samples> {
-    let problem_url = str_format(PROBLEM_URL.to_string(), problem_str_info);
+fn sample_index(header: &str) -> usize {
+    header
+        .chars()
+        .filter(|c| c.is_ascii_digit())
+        .collect::<String>()
+        .parse()
+        .unwrap()
+}
+
+// (見出し, preの中身)の組から入力例と出力例を取り出す
+fn collect_samples(
+    headed_pres: &[(String, String)],
+    input_header: &str,
+    output_header: &str,
+) -> (SampleCases, SampleCases) {
+    let mut inputs: SampleCases = Vec::new();
+    let mut outputs: SampleCases = Vec::new();
+    for (header, content) in headed_pres {
+        if header.contains(input_header) {
+            inputs.push((sample_index(header), content.clone()));
+        } else if header.contains(output_header) {
+            outputs.push((sample_index(header), content.clone()));
+        }
+    }
+    (inputs, outputs)
+}
+
+pub fn get_language(config_str_map: &ConfigStrMap) -> Result<&'static str> {
+    let language = config_str_map
+        .get("language")
+        .map_or(DEFAULT_LANGUAGE, |language| language.as_str());
+    SAMPLE_HEADERS
+        .iter()
+        .map(|(lang, _, _)| *lang)
+        .find(|lang| *lang == language)
+        .with_context(|| format!("Unsupported language: {}. Please use ja or en.", language))
+}
+
+pub fn get_problem_url(problem_str_info: &ProblemStrInfo, acn: &ACN) -> Result<String> {
+    let mut data_map = problem_str_info.clone();
+    data_map.insert(
+        "language".to_string(),
+        get_language(&acn.config_str_map)?.to_string(),
+    );
+    Ok(str_format(PROBLEM_URL.to_string(), &data_map))
+}
+
+async fn fetch_samples(problem_str_info: &ProblemStrInfo, acn: &ACN) -> Result<Samples> {
+    let problem_url = get_problem_url(problem_str_info, acn)?;
     let cookies = load_cookie_headers()?;
     let resp = acn
         .client
@@ -341,9 +594,7 @@ pub async fn get_sample_cases(
 
     let h3_selector = Selector::parse("h3").unwrap();
 
-    let mut inputs: Vec<(usize, String)> = Vec::new();
-    let mut outputs: Vec<(usize, String)> = Vec::new();
-
+    let mut headed_pres: Vec<(String, String)> = Vec::new();
     for pre_element in pre_elements {
         let pre_content = pre_element.text().collect::<String>();
         let mut h3_content: Option<String> = None;
@@ -360,42 +611,254 @@ pub async fn get_sample_cases(
             cursor = node.parent();
         }
         let h3_content = h3_content.context(PARSE_ERROR)?;
-        let is_input = h3_content.contains(INPUT_HEADER);
-        let is_output = h3_content.contains(OUTPUT_HEADER);
-        if is_input {
-            let index: usize = h3_content
-                .chars()
-                .filter(|c| c.is_ascii_digit())
-                .collect::<String>()
-                .parse()
-                .unwrap();
-            inputs.push((index, pre_content));
-        } else if is_output {
-            let index: usize = h3_content
-                .chars()
-                .filter(|c| c.is_ascii_digit())
-                .collect::<String>()
-                .parse()
-                .unwrap();
-            outputs.push((index, pre_content));
+        headed_pres.push((h3_content, pre_content));
+    }
+
+    // 問題文は日英両方を含むので, 設定した言語の見出しを優先し, なければ他の言語を使う
+    let language = get_language(&acn.config_str_map)?;
+    let mut headers: Vec<&(&str, &str, &str)> = SAMPLE_HEADERS.iter().collect();
+    headers.sort_by_key(|(lang, _, _)| *lang != language);
+    let (mut inputs, mut outputs) = (Vec::new(), Vec::new());
+    for (_, input_header, output_header) in headers {
+        (inputs, outputs) = collect_samples(&headed_pres, input_header, output_header);
+        if !inputs.is_empty() || !outputs.is_empty() {
+            break;
         }
     }
-    if let Some(target) = sample_case_id_arg {
-        inputs.retain(|x| x.0 == target);
-        outputs.retain(|x| x.0 == target);
+    // インタラクティブな問題には入力例と出力例の組がないことがある
+    let is_interactive = is_interactive_problem(&body);
+    if is_interactive && inputs.len() != outputs.len() {
+        inputs.clear();
+        outputs.clear();
     }
-
-    let size = match inputs.len() == outputs.len() {
-        true if !inputs.is_empty() => Some(inputs.len()),
-        _ => None,
+    // 読み取りに失敗した結果はキャッシュしない
+    if inputs.len() != outputs.len() || (inputs.is_empty() && !is_interactive) {
+        return Err(anyhow!(PARSE_ERROR));
     }
-    .expect(PARSE_ERROR);
 
     let samples = Samples {
+        size: inputs.len(),
         inputs,
         outputs,
-        size,
+        time_limit_ms: parse_time_limit_ms(&body),
+        memory_limit_mb: parse_memory_limit_mb(&body),
+        score: parse_score(&body),
+        eps: parse_eps(&body),
+        is_interactive,
+        statements: parse_statements(&doc),
+        custom_cases: vec![],
     };
 
     Ok(samples)
 }
+
+pub async fn get_sample_cases(
+    problem_str_info: &ProblemStrInfo,
+    acn: &ACN,
+    sample_case_id_arg: Option<usize>,
+) -> Result<Samples> {
+    let samples = load_or_fetch(
+        acn.cache_mode,
+        &samples_cache_path(problem_str_info, get_language(&acn.config_str_map)?)?,
+        fetch_samples(problem_str_info, acn),
+    )
+    .await?;
+    filter_samples(samples, sample_case_id_arg)
+}
+
+// -sで指定されたサンプルだけを残す
+fn filter_samples(mut samples: Samples, sample_case_id_arg: Option<usize>) -> Result<Samples> {
+    if samples.inputs.len() != samples.outputs.len()
+        || (samples.inputs.is_empty() && !samples.is_interactive)
+    {
+        return Err(anyhow!(PARSE_ERROR));
+    }
+    if let Some(target) = sample_case_id_arg {
+        samples.inputs.retain(|x| x.0 == target);
+        samples.outputs.retain(|x| x.0 == target);
+        if samples.inputs.is_empty() {
+            return Err(anyhow!("sample {} not found", target));
+        }
+    }
+    samples.size = samples.inputs.len();
+
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_find_task() {
+        let tasks: Vec<Task> = ["A", "B1", "Ex", "001"]
+            .iter()
+            .map(|label| Task {
+                label: label.to_string(),
+                task_screen_name: format!("x_{}", label.to_lowercase()),
+            })
+            .collect();
+        assert_eq!("x_a", find_task(&tasks, "a").unwrap().task_screen_name);
+        assert_eq!("x_b1", find_task(&tasks, "b1").unwrap().task_screen_name);
+        assert_eq!("x_ex", find_task(&tasks, "EX").unwrap().task_screen_name);
+        assert_eq!("x_001", find_task(&tasks, "001").unwrap().task_screen_name);
+        assert_eq!("x_ex", find_task(&tasks, "h").unwrap().task_screen_name);
+        assert!(find_task(&tasks, "i").is_none());
+
+        let tasks: Vec<Task> = ["G", "H"]
+            .iter()
+            .map(|label| Task {
+                label: label.to_string(),
+                task_screen_name: format!("x_{}", label.to_lowercase()),
+            })
+            .collect();
+        assert_eq!("x_h", find_task(&tasks, "h").unwrap().task_screen_name);
+        assert_eq!("x_h", find_task(&tasks, "ex").unwrap().task_screen_name);
+    }
+
+    #[test]
+    fn test_set_task() {
+        let tasks = vec![Task {
+            label: "Ex".to_string(),
+            task_screen_name: "abc300_h".to_string(),
+        }];
+        let mut problem_info = ProblemInfo {
+            contest: "abc300".to_string(),
+            problem_id: "h".to_string(),
+            task_screen_name: String::new(),
+            time_limit_ms: None,
+            memory_limit_mb: None,
+            score: None,
+        };
+        let mut problem_str_info: ProblemStrInfo =
+            HashMap::from([("problem_id".to_string(), "h".to_string())]);
+        let task = find_task(&tasks, "h").unwrap();
+        set_task(task, &mut problem_info, &mut problem_str_info);
+        assert_eq!("ex", problem_info.problem_id);
+        assert_eq!("abc300_h", problem_info.task_screen_name);
+        assert_eq!("ex", problem_str_info["problem_id"]);
+        assert_eq!("abc300_h", problem_str_info["task_screen_name"]);
+    }
+
+    #[test]
+    fn test_find_task_by_screen_name() {
+        let tasks = vec![Task {
+            label: "A".to_string(),
+            task_screen_name: "abc001_1".to_string(),
+        }];
+        let task = find_task_by_screen_name(&tasks, "abc001_1").unwrap();
+        assert_eq!("A", task.label);
+        assert!(find_task_by_screen_name(&tasks, "abc001_2").is_none());
+    }
+
+    #[test]
+    fn test_collect_samples() {
+        let headed_pres: Vec<(String, String)> = [
+            ("入力", "N"),
+            ("入力例 1", "3\n"),
+            ("出力例 1", "6\n"),
+            ("Input", "N"),
+            ("Sample Input 1", "3\n"),
+            ("Sample Output 1", "6\n"),
+            ("Sample Input 2", "0\n"),
+            ("Sample Output 2", "0\n"),
+        ]
+        .iter()
+        .map(|(h, c)| (h.to_string(), c.to_string()))
+        .collect();
+
+        let (inputs, outputs) = collect_samples(&headed_pres, "入力例", "出力例");
+        assert_eq!(vec![(1, "3\n".to_string())], inputs);
+        assert_eq!(vec![(1, "6\n".to_string())], outputs);
+
+        let (inputs, outputs) = collect_samples(&headed_pres, "Sample Input", "Sample Output");
+        assert_eq!(vec![(1, "3\n".to_string()), (2, "0\n".to_string())], inputs);
+        assert_eq!(2, outputs.len());
+    }
+
+    #[test]
+    fn test_parse_start_time() {
+        let body = r#"var startTime = moment("2024-04-20T21:00:00+09:00");"#;
+        let start_time = parse_start_time(body).unwrap();
+        assert_eq!(1713614400, start_time.unix_timestamp());
+        assert_eq!(None, parse_start_time("<html></html>"));
+    }
+
+    #[test]
+    fn test_parse_time_limit_ms() {
+        let body = "<p>実行時間制限: 2 sec / メモリ制限: 1024 MB</p>";
+        assert_eq!(Some(2000), parse_time_limit_ms(body));
+
+        let body = "<p>Time Limit: 2.5 sec / Memory Limit: 1024 MB</p>";
+        assert_eq!(Some(2500), parse_time_limit_ms(body));
+
+        assert_eq!(None, parse_time_limit_ms("<p>no limit</p>"));
+    }
+
+    #[test]
+    fn test_parse_memory_limit_mb() {
+        let body = "<p>実行時間制限: 2 sec / メモリ制限: 1024 MiB</p>";
+        assert_eq!(Some(1024), parse_memory_limit_mb(body));
+
+        let body = "<p>Time Limit: 2 sec / Memory Limit: 256 MB</p>";
+        assert_eq!(Some(256), parse_memory_limit_mb(body));
+    }
+
+    #[test]
+    fn test_parse_user_screen_name() {
+        let body = r#"<script>var userScreenName = "chokudai";</script>"#;
+        assert_eq!(Some("chokudai".to_string()), parse_user_screen_name(body));
+        let body = r#"<script>var userScreenName = "";</script>"#;
+        assert_eq!(None, parse_user_screen_name(body));
+    }
+
+    #[test]
+    fn test_parse_score() {
+        assert_eq!(Some(100), parse_score("<p>配点 : <var>100</var> 点</p>"));
+        assert_eq!(
+            Some(525),
+            parse_score("<p>Score : <var>525</var> points</p>")
+        );
+        assert_eq!(None, parse_score("<p>問題文</p>"));
+    }
+
+    #[test]
+    fn test_is_interactive_problem() {
+        assert!(is_interactive_problem(
+            "<p>この問題はインタラクティブな問題です。</p>"
+        ));
+        assert!(is_interactive_problem(
+            "<p>This is an interactive task.</p>"
+        ));
+        assert!(!is_interactive_problem(
+            "<p>入力は以下の形式で与えられる。</p>"
+        ));
+    }
+
+    #[test]
+    fn test_parse_eps() {
+        let body = "<p>ジャッジの出力との絶対誤差または相対誤差が <var>10^{-6}</var> 以下のとき正解と判定されます。</p>";
+        assert_eq!(Some(1e-6), parse_eps(body));
+
+        let body = "<p>Your output is considered correct when its absolute or relative error from the judge's output is at most <var>10^{-9}</var>.</p>";
+        assert_eq!(Some(1e-9), parse_eps(body));
+
+        assert_eq!(None, parse_eps("<p>1 \\leq N \\leq 10^5</p>"));
+    }
+
+    #[test]
+    fn test_filter_samples() {
+        let cases: SampleCases = (1..=3).map(|i| (i, format!("{}\n", i))).collect();
+        let samples = || Samples {
+            inputs: cases.clone(),
+            outputs: cases.clone(),
+            ..Default::default()
+        };
+        assert_eq!(3, filter_samples(samples(), None).unwrap().size);
+        let filtered = filter_samples(samples(), Some(2)).unwrap();
+        assert_eq!(vec![(2, "2\n".to_string())], filtered.inputs);
+        assert_eq!(1, filtered.size);
+        assert!(filter_samples(samples(), Some(9)).is_err());
+        assert!(filter_samples(Samples::default(), None).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::{anyhow, Result};
+use serde::{de::DeserializeOwned, Serialize};
+use shellexpand::full;
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use crate::config::ProblemStrInfo;
+use crate::util::str_format;
+
+const CONTEST_CACHE_DIR: &str = "~/.ac-ninja/cache/{{contest}}";
+const TASKS_FILE: &str = "tasks.toml";
+const SAMPLES_FILE: &str = "samples.toml";
+const SYNC_FILE: &str = "sync.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheMode {
+    // キャッシュがあればそれを使い, なければ取得して保存する
+    #[default]
+    Normal,
+    // ネットワークには一切アクセスしない
+    Offline,
+    // キャッシュを無視して取得し直す
+    Refresh,
+}
+
+impl CacheMode {
+    pub fn from_flags(offline: bool, refresh: bool) -> Self {
+        if offline {
+            CacheMode::Offline
+        } else if refresh {
+            CacheMode::Refresh
+        } else {
+            CacheMode::Normal
+        }
+    }
+}
+
+fn contest_cache_dir(problem_str_info: &ProblemStrInfo) -> Result<PathBuf> {
+    let dir = str_format(CONTEST_CACHE_DIR.to_string(), problem_str_info);
+    Ok(PathBuf::from(full(&dir)?.to_string()))
+}
+
+pub fn tasks_cache_path(problem_str_info: &ProblemStrInfo) -> Result<PathBuf> {
+    Ok(contest_cache_dir(problem_str_info)?.join(TASKS_FILE))
+}
+
+// 問題文とサンプルの見出しは言語ごとに違うので, 言語ごとに分けて保存する
+pub fn samples_cache_path(problem_str_info: &ProblemStrInfo, language: &str) -> Result<PathBuf> {
+    Ok(contest_cache_dir(problem_str_info)?
+        .join(&problem_str_info["task_screen_name"])
+        .join(language)
+        .join(SAMPLES_FILE))
+}
+
+pub fn sync_state_path(problem_str_info: &ProblemStrInfo) -> Result<PathBuf> {
+    Ok(contest_cache_dir(problem_str_info)?.join(SYNC_FILE))
+}
+
+// 壊れたキャッシュは無いものとして扱う
+pub fn load<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let contents = fs::read_to_string(path).ok()?;
+    toml::from_str(&contents).ok()
+}
+
+pub fn save<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, toml::to_string(value)?)?;
+    Ok(())
+}
+
+// modeに従ってキャッシュを読むか, fetchで取得してキャッシュに保存する
+pub async fn load_or_fetch<T, F>(mode: CacheMode, path: &Path, fetch: F) -> Result<T>
+where
+    T: Serialize + DeserializeOwned,
+    F: Future<Output = Result<T>>,
+{
+    if mode != CacheMode::Refresh {
+        if let Some(value) = load(path) {
+            return Ok(value);
+        }
+    }
+    if mode == CacheMode::Offline {
+        return Err(anyhow!(
+            "No cache found at {}. Run once without --offline to fetch it.",
+            path.display()
+        ));
+    }
+    let value = fetch.await?;
+    save(path, &value)?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::TempDir;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Value {
+        items: Vec<(usize, String)>,
+    }
+
+    fn value(s: &str) -> Value {
+        Value {
+            items: vec![(1, s.to_string())],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_or_fetch() {
+        let dir = TempDir::new("cache").unwrap();
+        let path = dir.path().join("value.toml");
+
+        let result = load_or_fetch::<Value, _>(CacheMode::Offline, &path, async {
+            panic!("offline mode must not fetch")
+        })
+        .await;
+        assert!(result.is_err());
+
+        let fetched = load_or_fetch(CacheMode::Normal, &path, async { Ok(value("1 2\n")) })
+            .await
+            .unwrap();
+        assert_eq!(value("1 2\n"), fetched);
+
+        let cached = load_or_fetch::<Value, _>(CacheMode::Offline, &path, async {
+            panic!("offline mode must not fetch")
+        })
+        .await
+        .unwrap();
+        assert_eq!(value("1 2\n"), cached);
+
+        let refreshed = load_or_fetch(CacheMode::Refresh, &path, async { Ok(value("3\n")) })
+            .await
+            .unwrap();
+        assert_eq!(value("3\n"), refreshed);
+    }
+}
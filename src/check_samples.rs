@@ -1,40 +1,64 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::*;
-use prettytable::{format, row, table, Table};
+use prettytable::{format, row, table, Cell, Row, Table};
 use std::collections::HashMap;
-use std::io::Write;
-use std::process::{Command, Stdio};
+use std::fs;
+use std::io::{Read, Write};
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use crate::ac_scraper::Samples;
 use crate::config::{
-    make_compile_command, make_execute_command, ConfigMap, ConfigStrMap, ConfigValue,
-    ProblemStrInfo,
+    make_checker_command, make_compile_command, make_execute_command, ConfigMap, ConfigStrMap,
+    ConfigValue, ProblemStrInfo,
 };
-use crate::util::split_one_line_command;
+use crate::custom_cases::TestCase;
+use crate::util::{split_one_line_command, TempDir};
 
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Clone, Copy)]
 #[allow(clippy::upper_case_acronyms)]
 pub enum Status {
     AC,
     WA,
+    TLE,
+    MLE,
+    RE,
     CE,
+    NoVerdict,
 }
 
+const DEFAULT_TIME_LIMIT_MS: u64 = 2000;
+const MEMORY_WARNING_RATIO: f64 = 0.9;
+// チェッカーが止まらない場合に全体が止まらないようにする
+const CHECKER_TIME_LIMIT: Duration = Duration::from_secs(10);
+
 impl Status {
     pub fn as_display_string(&self) -> ColoredString {
         match self {
             Status::AC => "AC".green(),
             Status::WA => "!! WA !!".red(),
+            Status::TLE => "!! TLE !!".yellow(),
+            Status::MLE => "!! MLE !!".yellow(),
+            Status::RE => "!! RE !!".magenta(),
             Status::CE => "!! CE !!".yellow(),
+            Status::NoVerdict => "--".normal(),
         }
     }
 }
 
 pub struct FailedDetail {
-    pub index: usize,
+    pub name: String,
+    pub status: Status,
+    pub message: Option<String>,
     pub input: String,
     pub expected: String,
     pub output: String,
+    pub stderr: String,
 }
 
 pub struct SampleResults {
@@ -42,14 +66,17 @@ pub struct SampleResults {
     pub failed_details: Vec<FailedDetail>,
 }
 
-fn add_total_status_to_table(mut table: Table, total_status: &Status) -> Table {
+pub fn add_total_status_to_table(mut table: Table, total_status: &Status) -> Table {
     let mut total_table = table!([c => "Total", total_status.as_display_string().reverse()]);
     total_table.set_format(*format::consts::FORMAT_BOX_CHARS);
-    table.add_row(row![cH2 => total_table]);
+    let span = table.get_row(0).map_or(1, |header| header.len());
+    table.add_row(Row::new(vec![Cell::new(&total_table.to_string())
+        .style_spec("c")
+        .with_hspan(span)]));
     table
 }
 
-fn compile(problem_str_info: &ProblemStrInfo, config_str_map: &ConfigStrMap) -> Result<Status> {
+pub fn compile(problem_str_info: &ProblemStrInfo, config_str_map: &ConfigStrMap) -> Result<Status> {
     let mut compile_config: HashMap<String, String> = HashMap::new();
     compile_config.extend(config_str_map.iter().map(|(k, v)| (k.clone(), v.clone())));
     compile_config.extend(problem_str_info.iter().map(|(k, v)| (k.clone(), v.clone())));
@@ -106,7 +133,212 @@ pub fn execute_with_manual_input(
     Ok(())
 }
 
-fn is_ac(expected: &str, output: &str) -> bool {
+pub struct ExecutionResult {
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_status: Option<ExitStatus>,
+    pub timed_out: bool,
+    pub wall_time: Duration,
+    pub cpu_time: Duration,
+    pub peak_memory_kb: u64,
+}
+
+fn timeval_to_duration(tv: libc::timeval) -> Duration {
+    Duration::from_secs(tv.tv_sec as u64) + Duration::from_micros(tv.tv_usec as u64)
+}
+
+// wait4で終了を待ち, リソース使用量も取得する. blockしない場合, 終了していなければNone
+fn wait_with_rusage(pid: u32, block: bool) -> Result<Option<(ExitStatus, libc::rusage)>> {
+    let options = if block { 0 } else { libc::WNOHANG };
+    loop {
+        let mut status: libc::c_int = 0;
+        let mut rusage: libc::rusage = unsafe { std::mem::zeroed() };
+        let ret = unsafe { libc::wait4(pid as libc::pid_t, &mut status, options, &mut rusage) };
+        match ret {
+            0 => return Ok(None),
+            -1 => {
+                let err = std::io::Error::last_os_error();
+                if err.kind() != std::io::ErrorKind::Interrupted {
+                    return Err(err.into());
+                }
+            }
+            _ => return Ok(Some((ExitStatus::from_raw(status), rusage))),
+        }
+    }
+}
+
+fn max_rss_kb(rusage: &libc::rusage) -> u64 {
+    // macOSではバイト単位
+    if cfg!(target_os = "macos") {
+        rusage.ru_maxrss as u64 / 1024
+    } else {
+        rusage.ru_maxrss as u64
+    }
+}
+
+fn signal_name(signal: i32) -> String {
+    match signal {
+        4 => "SIGILL".to_string(),
+        6 => "SIGABRT".to_string(),
+        7 => "SIGBUS".to_string(),
+        8 => "SIGFPE".to_string(),
+        9 => "SIGKILL".to_string(),
+        11 => "SIGSEGV".to_string(),
+        _ => format!("signal {}", signal),
+    }
+}
+
+// 正常終了していない場合はその理由を返す
+pub fn get_runtime_error(exit_status: &ExitStatus) -> Option<String> {
+    if let Some(signal) = exit_status.signal() {
+        return Some(format!("Terminated by {}", signal_name(signal)));
+    }
+    match exit_status.code() {
+        Some(0) => None,
+        Some(code) => Some(format!("Exit code {}", code)),
+        None => Some("Terminated abnormally".to_string()),
+    }
+}
+
+fn format_memory(kb: u64) -> String {
+    format!("{:.1} MB", kb as f64 / 1024.0)
+}
+
+pub fn get_time_limit(time_limit_ms: Option<u64>, config_map: &ConfigMap) -> Duration {
+    let ms = match config_map.get("timeout_ms") {
+        Some(ConfigValue::Integer(ms)) if *ms > 0 => *ms as u64,
+        _ => time_limit_ms.unwrap_or(DEFAULT_TIME_LIMIT_MS),
+    };
+    Duration::from_millis(ms)
+}
+
+// cargo runやsh run.shのようなラッパーの先の孫プロセスもまとめてkillする
+// プロセスグループはprocess_group(0)で子プロセスのpidと同じになっている
+pub fn kill_process_group(pid: u32) {
+    unsafe {
+        libc::kill(-(pid as libc::pid_t), libc::SIGKILL);
+    }
+}
+
+// 制限時間を超えたら子プロセスをプロセスグループごとkillする
+// 孫プロセスが残るとstdoutが閉じずに読み込みが終わらないため
+// 子プロセスはwait4で回収するのでChild::waitは呼ばない
+#[allow(clippy::zombie_processes)]
+pub fn execute_with_time_limit(
+    command: &str,
+    args: &[&str],
+    input: String,
+    time_limit: Duration,
+) -> Result<ExecutionResult> {
+    let mut child = Command::new(command)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0)
+        .spawn()
+        .with_context(|| format!("Failed to run {} {}", command, args.join(" ")))?;
+    let start = Instant::now();
+    let mut stdin = child.stdin.take().expect("Failed to open stdin!");
+    thread::spawn(move || {
+        // 途中でkillされた場合はBrokenPipeになるので無視する
+        let _ = stdin.write_all(input.as_bytes());
+    });
+    let mut stdout = child.stdout.take().expect("Failed to open stdout!");
+    let stdout_reader = thread::spawn(move || {
+        let mut buf: Vec<u8> = Vec::new();
+        let _ = stdout.read_to_end(&mut buf);
+        buf
+    });
+    let mut stderr = child.stderr.take().expect("Failed to open stderr!");
+    let stderr_reader = thread::spawn(move || {
+        let mut buf: Vec<u8> = Vec::new();
+        let _ = stderr.read_to_end(&mut buf);
+        buf
+    });
+
+    let pid = child.id();
+    let (exit_status, timed_out, rusage) = loop {
+        if let Some((exit_status, rusage)) = wait_with_rusage(pid, false)? {
+            break (Some(exit_status), false, rusage);
+        }
+        if start.elapsed() >= time_limit {
+            kill_process_group(pid);
+            let (_, rusage) = wait_with_rusage(pid, true)?.context("Failed to wait child!")?;
+            break (None, true, rusage);
+        }
+        thread::sleep(Duration::from_millis(5));
+    };
+    let wall_time = start.elapsed();
+    let raw_output = stdout_reader.join().expect("Failed to read stdout!");
+    let raw_error = stderr_reader.join().expect("Failed to read stderr!");
+
+    Ok(ExecutionResult {
+        stdout: String::from_utf8_lossy(&raw_output).to_string(),
+        stderr: String::from_utf8_lossy(&raw_error).to_string(),
+        exit_status,
+        timed_out,
+        wall_time,
+        cpu_time: timeval_to_duration(rusage.ru_utime) + timeval_to_duration(rusage.ru_stime),
+        peak_memory_kb: max_rss_kb(&rusage),
+    })
+}
+
+// 計測がぶれないようにコア数より多くは並列に実行しない
+fn get_jobs(config_map: &ConfigMap, size: usize) -> usize {
+    let cores = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    let jobs = match config_map.get("jobs") {
+        Some(ConfigValue::Integer(jobs)) if *jobs > 0 => *jobs as usize,
+        _ => cores,
+    };
+    jobs.min(cores).min(size).max(1)
+}
+
+fn execute_samples(
+    command: &str,
+    args: &[&str],
+    cases: &[TestCase],
+    time_limit: Duration,
+    jobs: usize,
+) -> Result<Vec<ExecutionResult>> {
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<ExecutionResult>>>> =
+        Mutex::new((0..cases.len()).map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                if i >= cases.len() {
+                    break;
+                }
+                let input = cases[i].input.clone();
+                let result = execute_with_time_limit(command, args, input, time_limit);
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("Sample was not executed!"))
+        .collect()
+}
+
+pub fn is_same_token(expected: &str, output: &str, eps: Option<f64>) -> bool {
+    if expected == output {
+        return true;
+    }
+    let (Some(eps), Ok(e), Ok(o)) = (eps, expected.parse::<f64>(), output.parse::<f64>()) else {
+        return false;
+    };
+    let diff = (e - o).abs();
+    diff <= eps || diff <= eps * e.abs()
+}
+
+pub fn is_ac(expected: &str, output: &str, eps: Option<f64>) -> bool {
     let expected_vec: Vec<&str> = expected
         .split([' ', '\n'])
         .filter(|s| !s.is_empty())
@@ -115,7 +347,64 @@ fn is_ac(expected: &str, output: &str) -> bool {
         .split([' ', '\n'])
         .filter(|s| !s.is_empty())
         .collect();
-    expected_vec == output_vec
+    expected_vec.len() == output_vec.len()
+        && expected_vec
+            .iter()
+            .zip(output_vec.iter())
+            .all(|(e, o)| is_same_token(e, o, eps))
+}
+
+// testlib形式のチェッカーで判定する. 終了コードが0ならAC
+fn check_with_checker(
+    check_config: &HashMap<String, String>,
+    sample_id: usize,
+    input: &str,
+    expected: &str,
+    output: &str,
+) -> Result<(bool, String)> {
+    let work_dir = TempDir::new(&format!("checker-{}", sample_id))?;
+    run_checker(check_config, work_dir.path(), input, expected, output)
+}
+
+fn run_checker(
+    check_config: &HashMap<String, String>,
+    work_dir: &Path,
+    input: &str,
+    expected: &str,
+    output: &str,
+) -> Result<(bool, String)> {
+    let mut files: HashMap<String, String> = HashMap::new();
+    for (key, file_name, content) in [
+        ("input_file", "input.txt", input),
+        ("expected_file", "expected.txt", expected),
+        ("output_file", "output.txt", output),
+    ] {
+        let path = work_dir.join(file_name);
+        fs::write(&path, content)?;
+        files.insert(key.to_string(), path.to_str().unwrap().to_string());
+    }
+
+    let mut checker_config = check_config.clone();
+    checker_config.extend(files);
+    let checker_command = make_checker_command(checker_config)?;
+    let (command, args) = split_one_line_command(&checker_command);
+    let checker_output = execute_with_time_limit(command, &args, String::new(), CHECKER_TIME_LIMIT)
+        .with_context(|| format!("Failed to run checker: {}", command))?;
+    if checker_output.timed_out {
+        return Ok((
+            false,
+            format!(
+                "Checker timed out after {} ms",
+                CHECKER_TIME_LIMIT.as_millis()
+            ),
+        ));
+    }
+
+    let message = format!("{}{}", checker_output.stdout, checker_output.stderr);
+    let ok = checker_output
+        .exit_status
+        .is_some_and(|status| status.success());
+    Ok((ok, message.trim().to_string()))
 }
 
 pub fn sample_check(
@@ -124,7 +413,7 @@ pub fn sample_check(
     config_str_map: &ConfigStrMap,
     config_map: &ConfigMap,
 ) -> Result<SampleResults> {
-    let mut table = table!([c => "Sample", "Status"]);
+    let mut table = table!([c => "Sample", "Status", "Time", "CPU", "Memory"]);
     let mut failed_details: Vec<FailedDetail> = vec![];
     table.set_format(*format::consts::FORMAT_BOX_CHARS);
 
@@ -150,50 +439,124 @@ pub fn sample_check(
     let mut execute_config: HashMap<String, String> = HashMap::new();
     execute_config.extend(config_str_map.iter().map(|(k, v)| (k.clone(), v.clone())));
     execute_config.extend(problem_str_info.iter().map(|(k, v)| (k.clone(), v.clone())));
-    let execute_command = make_execute_command(execute_config)?;
+    let execute_command = make_execute_command(execute_config.clone())?;
     let (command, args) = split_one_line_command(&execute_command);
+    let use_checker = execute_config.contains_key("checker_command");
 
     println!("{}: {}", "Execute command".green(), command);
     println!("{}: {:?}", "Execute arguments".green(), args);
+    if use_checker {
+        println!(
+            "{}: {}",
+            "Checker command".green(),
+            execute_config["checker_command"]
+        );
+    }
+    let time_limit = get_time_limit(samples.time_limit_ms, config_map);
+    println!("{}: {} ms", "Time limit".green(), time_limit.as_millis());
+    if let Some(eps) = samples.eps {
+        println!("{}: {:e}", "Tolerance".green(), eps);
+    }
+    let memory_limit_kb = samples.memory_limit_mb.map(|mb| mb * 1024);
+    if let Some(memory_limit_mb) = samples.memory_limit_mb {
+        println!("{}: {} MB", "Memory limit".green(), memory_limit_mb);
+    }
+    // サンプルの後ろにtest_dirのケースを並べる
+    let mut cases: Vec<TestCase> = (0..samples.size)
+        .map(|i| TestCase {
+            name: samples.inputs[i].0.to_string(),
+            input: samples.inputs[i].1.clone(),
+            expected: Some(samples.outputs[i].1.clone()),
+        })
+        .collect();
+    cases.extend(samples.custom_cases.iter().cloned());
+    let jobs = get_jobs(config_map, cases.len());
+    println!("{}: {}", "Jobs".green(), jobs);
 
-    for i in 0..samples.size {
-        let sample_id = samples.inputs[i].clone().0;
-        let mut child = Command::new(command)
-            .args(&args)
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .spawn()
-            .expect("Failed to run!");
-        let mut stdin = child.stdin.take().expect("Failed to open stdin!");
-        let input: String = samples.inputs[i].clone().1;
-        std::thread::spawn(move || {
-            stdin
-                .write_all(input.as_bytes())
-                .expect("Failed to write to stdin!");
-        });
-        let raw_output = child.wait_with_output().expect("Failed to read stdout!");
-        let output_str = String::from_utf8_lossy(&raw_output.stdout).to_string();
-
-        let expected: String = samples.outputs[i].clone().1;
+    let results = execute_samples(command, &args, &cases, time_limit, jobs)?;
+    let mut unjudged_outputs: Vec<(String, String)> = vec![];
+    for (i, (case, result)) in cases.iter().zip(results).enumerate() {
+        let output_str = result.stdout;
 
-        let status = if is_ac(&expected, &output_str) {
+        let mut message = result.exit_status.as_ref().and_then(get_runtime_error);
+        let over_memory = memory_limit_kb.is_some_and(|limit| result.peak_memory_kb > limit);
+        let status = if result.timed_out {
+            Status::TLE
+        } else if over_memory {
+            message = Some(format!(
+                "Peak memory {} exceeds the limit",
+                format_memory(result.peak_memory_kb)
+            ));
+            Status::MLE
+        } else if message.is_some() {
+            Status::RE
+        } else if case.expected.is_none() {
+            Status::NoVerdict
+        } else if use_checker {
+            let (ok, checker_message) = check_with_checker(
+                &execute_config,
+                i,
+                &case.input,
+                case.expected.as_ref().unwrap(),
+                &output_str,
+            )?;
+            if !checker_message.is_empty() {
+                message = Some(checker_message);
+            }
+            if ok {
+                Status::AC
+            } else {
+                Status::WA
+            }
+        } else if is_ac(case.expected.as_ref().unwrap(), &output_str, samples.eps) {
             Status::AC
         } else {
-            total_status = Status::WA;
+            Status::WA
+        };
+        if status == Status::NoVerdict {
+            unjudged_outputs.push((case.name.clone(), output_str));
+        } else if status != Status::AC {
+            if total_status == Status::AC {
+                total_status = status;
+            }
             failed_details.push(FailedDetail {
-                index: sample_id,
-                input: samples.inputs[i].clone().1,
-                expected,
+                name: case.name.clone(),
+                status,
+                message,
+                input: case.input.clone(),
+                expected: case.expected.clone().unwrap_or_default(),
                 output: output_str,
+                stderr: result.stderr,
             });
-            Status::WA
+        }
+        let memory = format_memory(result.peak_memory_kb);
+        let memory = match memory_limit_kb {
+            Some(limit) if result.peak_memory_kb as f64 > limit as f64 * MEMORY_WARNING_RATIO => {
+                memory.yellow()
+            }
+            _ => memory.normal(),
         };
-        table.add_row(row![c => sample_id, status.as_display_string()]);
+        table.add_row(row![
+            c => case.name,
+            status.as_display_string(),
+            format!("{} ms", result.wall_time.as_millis()),
+            format!("{} ms", result.cpu_time.as_millis()),
+            memory
+        ]);
     }
 
     table = add_total_status_to_table(table, &total_status);
     table.printstd();
 
+    // 期待される出力がないケースは出力だけを表示する
+    for (name, output) in unjudged_outputs {
+        println!(
+            "{}",
+            format!("{:-^30}", format!(" Output of {} ", name)).blue()
+        );
+        println!("{}", output);
+    }
+
     Ok(SampleResults {
         total_status,
         failed_details,
@@ -201,15 +564,134 @@ pub fn sample_check(
 }
 
 pub fn display_failed_detail(failed_details: Vec<FailedDetail>) {
-    let mut table = table!(["Index", "Input", "Expected".green(), "Output".red()]);
+    let mut table = table!([
+        "Sample",
+        "Status",
+        "Input",
+        "Expected".green(),
+        "Output".red(),
+        "Stderr".yellow()
+    ]);
     table.set_format(*format::consts::FORMAT_BOX_CHARS);
     for detail in failed_details {
+        let status = match detail.message {
+            Some(message) => {
+                format!("{}\n{}", detail.status.as_display_string(), message)
+            }
+            None => detail.status.as_display_string().to_string(),
+        };
         table.add_row(row![
-            detail.index,
+            detail.name,
+            status,
             detail.input,
             detail.expected,
-            detail.output
+            detail.output,
+            detail.stderr
         ]);
     }
     table.printstd();
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_ac() {
+        assert!(is_ac("1 2\n3\n", "1 2 3", None));
+        assert!(!is_ac("1 2\n3\n", "1 2", None));
+        assert!(!is_ac("0.5\n", "0.5000001\n", None));
+
+        assert!(is_ac("0.5\n", "0.5000001\n", Some(1e-6)));
+        assert!(!is_ac("0.5\n", "0.50001\n", Some(1e-6)));
+        assert!(is_ac("1000000000.0\n", "1000000100.0\n", Some(1e-6)));
+        assert!(!is_ac("Yes\n", "No\n", Some(1e-6)));
+    }
+
+    #[test]
+    fn test_execute_with_time_limit_kills_grandchild() {
+        let start = Instant::now();
+        // shがsleepを孫プロセスとして起動するように, 後ろにコマンドを続ける
+        let result = execute_with_time_limit(
+            "sh",
+            &["-c", "sleep 100; echo done"],
+            String::new(),
+            Duration::from_millis(200),
+        )
+        .unwrap();
+        assert!(result.timed_out);
+        assert_eq!("", result.stdout);
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_check_with_checker() {
+        let mut check_config: HashMap<String, String> = HashMap::new();
+        check_config.insert(
+            "checker_command".to_string(),
+            "cmp -s {{output_file}} {{expected_file}}".to_string(),
+        );
+        let (ok, _) = check_with_checker(&check_config, 1, "1\n", "2\n", "2\n").unwrap();
+        assert!(ok);
+        let (ok, _) = check_with_checker(&check_config, 2, "1\n", "2\n", "3\n").unwrap();
+        assert!(!ok);
+    }
+
+    #[test]
+    fn test_execute_samples() {
+        let cases: Vec<TestCase> = (1..=3)
+            .map(|i| TestCase {
+                name: i.to_string(),
+                input: format!("{}\n", i),
+                expected: None,
+            })
+            .collect();
+        let results = execute_samples("cat", &[], &cases, Duration::from_secs(5), 2).unwrap();
+        let outputs: Vec<String> = results.into_iter().map(|r| r.stdout).collect();
+        assert_eq!(vec!["1\n", "2\n", "3\n"], outputs);
+    }
+
+    #[test]
+    fn test_execute_with_time_limit() {
+        let result =
+            execute_with_time_limit("cat", &[], "1 2\n".to_string(), Duration::from_secs(5))
+                .unwrap();
+        assert!(!result.timed_out);
+        assert_eq!("1 2\n", result.stdout);
+        assert!(result.peak_memory_kb > 0);
+
+        let result =
+            execute_with_time_limit("sleep", &["5"], "".to_string(), Duration::from_millis(100))
+                .unwrap();
+        assert!(result.timed_out);
+        assert!(result.wall_time >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_get_runtime_error() {
+        let result = execute_with_time_limit(
+            "sh",
+            &["-c", "exit 3"],
+            "".to_string(),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        assert_eq!(
+            Some("Exit code 3".to_string()),
+            result.exit_status.as_ref().and_then(get_runtime_error)
+        );
+
+        let result = execute_with_time_limit(
+            "sh",
+            &["-c", "echo err >&2; kill -SEGV $$"],
+            "".to_string(),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        assert_eq!("err\n", result.stderr);
+        assert_eq!(
+            Some("Terminated by SIGSEGV".to_string()),
+            result.exit_status.as_ref().and_then(get_runtime_error)
+        );
+    }
+}
@@ -3,13 +3,16 @@ use colored::*;
 use std::fmt;
 use std::{collections::HashMap, env, fs, io::Write};
 
-use crate::ac_scraper::add_task_name_to_problem_info;
+use crate::ac_scraper::{
+    add_task_name_to_problem_info, find_task_by_screen_name, get_cached_task_list,
+};
 use crate::data::ACN;
 use crate::{util::*, GlobalArgs};
 
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use shellexpand::full;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 const CONFIG_DIR: &str = "~/.config/ac-ninja";
 const CONFIG_PATH: &str = "~/.config/ac-ninja/config.toml";
@@ -20,7 +23,8 @@ const DEFAULT_CONFIG: &str = "#config.toml
 #
 # --------------------------------------------------------------------------------------
 # contest_dir:          ac-ninjaを実行するディレクトリです.
-#                       {{contesty_type}},{{contest_id}}を特定できる必要があります
+#                       {{contest}}, または{{contesty_type}},{{contest_id}}を
+#                       特定できる必要があります
 # --------------------------------------------------------------------------------------
 # source_file_path:     ac-ninjaで扱うソースファイルのパスです.
 # --------------------------------------------------------------------------------------
@@ -29,14 +33,49 @@ const DEFAULT_CONFIG: &str = "#config.toml
 # --------------------------------------------------------------------------------------
 # execute_command:      プログラムを実行するためのコマンドです.
 # --------------------------------------------------------------------------------------
+# timeout_ms:           (任意) サンプル1ケースあたりの制限時間(ミリ秒)です.
+#                       省略した場合は問題ページの実行時間制限を使います.
+# --------------------------------------------------------------------------------------
+# jobs:                 (任意) サンプルを並列に実行する数です. コア数が上限です.
+# --------------------------------------------------------------------------------------
+# test_dir:             (任意) 自分で用意したテストケースを置くディレクトリです.
+#                       *.inと同名の*.outがサンプルと一緒に実行されます.
+# --------------------------------------------------------------------------------------
+# generator_command:    (任意) stressで使う入力の生成器のコマンドです.
+#                       {{seed}}にテストの番号が入ります.
+# --------------------------------------------------------------------------------------
+# brute_command:        (任意) stressで使う愚直解のコマンドです.
+# --------------------------------------------------------------------------------------
+# checker_command:      (任意) 出力を判定するチェッカーのコマンドです.
+#                       {{input_file}}, {{output_file}}, {{expected_file}}を含められます.
+#                       終了コードが0ならAC, それ以外ならWAになります.
+# --------------------------------------------------------------------------------------
+# interactor_command:   (任意) インタラクティブな問題で使うインタラクタのコマンドです.
+#                       終了コードが0ならAC, それ以外ならWAになります.
+# --------------------------------------------------------------------------------------
+# source_template:      (任意) prepareでソースファイルを作るときのテンプレートのパスです.
+# --------------------------------------------------------------------------------------
+# language:             (任意) 問題文の言語です. \"ja\"か\"en\"を指定します.
+#                       省略した場合は\"ja\"です.
+# --------------------------------------------------------------------------------------
+# username:             (任意) standingsで自分の行を探すユーザー名です.
+#                       省略した場合はログイン中のユーザー名を使います.
+# --------------------------------------------------------------------------------------
+# friends:              (任意) standingsで一緒に表示するユーザー名のリストです.
+# --------------------------------------------------------------------------------------
+# archive_path:         (任意) sync-submissionsでACしたソースを保存するパスです.
+#                       {{submission_id}}を含められます. 省略した場合はsource_file_pathです.
+# --------------------------------------------------------------------------------------
 # ファイルパスや, 実行コマンドには{{変数}}を含むことができます.
-# {{contest_type}}, {{contest_id}}, {{problem_id}}以外の変数は
+# {{contest}}, {{contest_type}}, {{contest_id}}, {{problem_id}}以外の変数は
 # config.toml内で解決可能である必要があります.
 #
 # {{CONTEST_TYPE}}のように大文字で記述すると, \"ABC\",\"ARC\",\"AGC\"のように
 # contest_typeが大文字であることを表します.
 # また, contest_idに関しては{{contest_id_0_pad}}とすることで, \"009\"のように
 # AtCoderのURLに沿った0埋めを表すことが出来ます.
+# {{contest}}は\"abc350\",\"typical90\"のようなURLに含まれるコンテスト名です.
+# 問題ページから読み取った{{time_limit_ms}}, {{memory_limit_mb}}, {{score}}も使えます(-iでは使えません).
 #
 # <設定例>
 # AtCoderにC++で参戦している人の例です.
@@ -50,7 +89,7 @@ const DEFAULT_CONFIG: &str = "#config.toml
 # compile_command = \"g++ {{source_file_path}} -std=c++17 -o {{output_file_path}}\"
 # execute_command = \"{{output_file_path}}\"
 ";
-const PROBLEM_INFO_PRINT_FORMAT: &str = "{{CONTEST_TYPE}} {{contest_id}} {{PROBLEM_ID}}";
+const PROBLEM_INFO_PRINT_FORMAT: &str = "{{CONTEST}} {{PROBLEM_ID}}";
 
 #[derive(Debug, Clone, Serialize, PartialEq)]
 pub enum ConfigValue {
@@ -219,126 +258,230 @@ pub struct Config {
 }
 
 #[derive(Debug)]
-#[allow(clippy::upper_case_acronyms)]
-pub enum ContestType {
-    ABC,
-    ARC,
-    AGC,
+pub struct ProblemInfo {
+    pub contest: String,
+    pub problem_id: String,
+    pub task_screen_name: String,
+    pub time_limit_ms: Option<u64>,
+    pub memory_limit_mb: Option<u64>,
+    pub score: Option<u64>,
 }
 
-impl fmt::Display for ContestType {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            ContestType::ABC => write!(f, "abc"),
-            ContestType::ARC => write!(f, "arc"),
-            ContestType::AGC => write!(f, "agc"),
+pub type ProblemStrInfo = HashMap<String, String>;
+
+// contest_dirの{{変数}}のうち, カレントディレクトリから読み取れるもの
+const CONTEST_DIR_PATTERNS: [(&str, &str); 4] = [
+    ("contest", "[A-Za-z0-9_-]+"),
+    ("contest_type", "[A-Za-z]+"),
+    ("contest_id", "[0-9]+"),
+    ("contest_id_0_pad", "[0-9]+"),
+];
+
+// contest_dirを正規表現にしてカレントディレクトリと照合し, コンテスト名を返す
+fn detect_contest(config_dir: &str, current_dir: &str) -> Option<String> {
+    let placeholder = Regex::new(r"\{\{(.+?)\}\}").unwrap();
+    let mut pattern = String::from("^");
+    let mut named: Vec<&str> = Vec::new();
+    let mut last = 0;
+    for caps in placeholder.captures_iter(config_dir) {
+        let whole = caps.get(0).unwrap();
+        pattern.push_str(&regex::escape(&config_dir[last..whole.start()]));
+        last = whole.end();
+        let key = caps[1].to_lowercase();
+        match CONTEST_DIR_PATTERNS.iter().find(|(name, _)| *name == key) {
+            Some((name, re)) if !named.contains(name) => {
+                named.push(name);
+                pattern.push_str(&format!("(?P<{}>{})", name, re));
+            }
+            Some((_, re)) => pattern.push_str(&format!("(?:{})", re)),
+            None => pattern.push_str("[^/]+"),
         }
     }
-}
+    pattern.push_str(&regex::escape(&config_dir[last..]));
+    pattern.push('$');
 
-impl ContestType {
-    pub fn from_str(v: &str) -> Option<Self> {
-        match v {
-            "abc" => Some(ContestType::ABC),
-            "arc" => Some(ContestType::ARC),
-            "agc" => Some(ContestType::AGC),
-            _ => None,
-        }
+    let caps = Regex::new(&pattern).ok()?.captures(current_dir)?;
+    let get = |name: &str| caps.name(name).map(|m| m.as_str().to_lowercase());
+    if let Some(contest) = get("contest") {
+        return Some(contest);
     }
+    let contest_type = get("contest_type")?;
+    let contest_id: i64 = get("contest_id")
+        .or_else(|| get("contest_id_0_pad"))?
+        .parse()
+        .ok()?;
+    Some(format!("{}{:0>3}", contest_type, contest_id))
 }
 
-#[derive(Debug)]
-pub struct ProblemInfo {
-    pub contest_type: ContestType,
-    pub contest_id: i64,
-    pub problem_id: char,
-    pub task_screen_name: String,
+// "https://atcoder.jp/contests/abc300/tasks/abc300_d"のような問題のURLから読み取る
+// problem_idはURLの末尾を使い, 問題一覧がキャッシュにあればその見出しに置き換える
+fn parse_task_url(url: &str) -> Option<ProblemInfo> {
+    let re = Regex::new(r"^https?://atcoder\.jp/contests/([^/?#]+)/tasks/([^/?#]+)").unwrap();
+    let caps = re.captures(url)?;
+    let task_screen_name = caps[2].to_string();
+    let problem_id = task_screen_name
+        .rsplit('_')
+        .next()
+        .unwrap_or(&task_screen_name)
+        .to_lowercase();
+    Some(ProblemInfo {
+        contest: caps[1].to_lowercase(),
+        problem_id,
+        task_screen_name,
+        time_limit_ms: None,
+        memory_limit_mb: None,
+        score: None,
+    })
 }
 
-pub type ProblemStrInfo = HashMap<String, String>;
-
-// configで定めた通りのファイルの時のみ
-pub async fn get_problem_info_from_path(
+// 問題のURLが渡された場合は, 問題一覧を取得せずにそこから読み取る
+pub async fn get_problem_info(
     acn: &ACN,
     config_str_map: &HashMap<String, String>,
-    problem_id: char,
-    args: &GlobalArgs,
+    problem_arg: &str,
+    contest_arg: &Option<String>,
+    contest_type_arg: &Option<String>,
+    contest_id_arg: Option<i64>,
 ) -> Result<(ProblemInfo, ProblemStrInfo)> {
+    if let Some(mut problem_info) = parse_task_url(problem_arg) {
+        // abc001_1のように見出しとURLの末尾が違う問題があるので, prepareで取得済みなら同じ見出しを使う
+        let contest_str_info = get_contest_str_info(&problem_info.contest);
+        if let Some(tasks) = get_cached_task_list(&contest_str_info) {
+            if let Some(task) = find_task_by_screen_name(&tasks, &problem_info.task_screen_name) {
+                problem_info.problem_id = task.label.to_lowercase();
+            }
+        }
+        let mut problem_str_info = get_problem_str_info(&problem_info);
+        problem_str_info.insert(
+            "task_screen_name".to_string(),
+            problem_info.task_screen_name.clone(),
+        );
+        return Ok((problem_info, problem_str_info));
+    }
+    get_problem_info_from_path(
+        acn,
+        config_str_map,
+        problem_arg,
+        contest_arg,
+        contest_type_arg,
+        contest_id_arg,
+    )
+    .await
+}
+
+// オプションで指定された値をカレントディレクトリから読み取った値より優先する
+pub fn get_contest(
+    config_str_map: &HashMap<String, String>,
+    contest_arg: &Option<String>,
+    contest_type_arg: &Option<String>,
+    contest_id_arg: Option<i64>,
+) -> Result<String> {
     let current_dir = env::current_dir()?.to_str().unwrap().to_string();
     let config_dir = str_format(config_str_map["contest_dir"].clone(), config_str_map);
 
-    let mut contest_type: Option<String> = if args.contest_type_arg.is_some() {
-        Some(args.contest_type_arg.clone().unwrap().as_str())
-    } else {
-        None
-    };
-    let mut contest_id = args.contest_id_arg;
-
-    for ct in ["abc", "arc", "agc"] {
-        for ci in 0i64..999i64 {
-            let mut mp: HashMap<String, String> = HashMap::new();
-            mp.insert("contest_type".to_string(), ct.to_string());
-            mp.insert("contest_id".to_string(), ci.to_string());
-            mp.insert("contest_id_0_pad".to_string(), format!("{:0>3}", ci));
-            let cand = str_format(config_dir.clone(), &mp);
-            if cand == current_dir {
-                if contest_type.is_none() {
-                    contest_type = Some(ct.to_string());
-                }
-                if contest_id.is_none() {
-                    contest_id = Some(ci);
-                }
-            }
+    let detected = detect_contest(&config_dir, &current_dir).map(|c| get_contest_str_info(&c));
+    let contest = match (contest_arg, contest_type_arg, contest_id_arg) {
+        (Some(contest), _, _) => Some(contest.to_lowercase()),
+        (None, None, None) => detected.map(|info| info["contest"].clone()),
+        (None, contest_type, contest_id) => {
+            let contest_type = contest_type
+                .clone()
+                .or_else(|| detected.as_ref()?.get("contest_type").cloned());
+            let contest_id =
+                contest_id.or_else(|| detected.as_ref()?.get("contest_id")?.parse().ok());
+            contest_type
+                .zip(contest_id)
+                .map(|(t, i)| format!("{}{:0>3}", t.to_lowercase(), i))
         }
-    }
+    };
 
-    if let (Some(contest_type), Some(contest_id)) = (contest_type.as_deref(), contest_id) {
-        let problem_info = ProblemInfo {
-            contest_type: ContestType::from_str(contest_type).unwrap(),
-            contest_id,
-            problem_id,
-            task_screen_name: "".into(),
-        };
-        let problem_str_info = get_problem_str_info(&problem_info);
-        let (problem_info, problem_str_info) =
-            add_task_name_to_problem_info(acn, problem_info, problem_str_info)
-                .await
-                .with_context(|| {
-                    "Failed to get task name. Please check you logged in and the contest exists"
-                })?;
-        return Ok((problem_info, problem_str_info));
+    contest.ok_or_else(|| anyhow!("If you are not in the directory configured, you need to specify the problem information with options."))
+}
+
+// configで定めた通りのファイルの時のみ
+async fn get_problem_info_from_path(
+    acn: &ACN,
+    config_str_map: &HashMap<String, String>,
+    problem_id: &str,
+    contest_arg: &Option<String>,
+    contest_type_arg: &Option<String>,
+    contest_id_arg: Option<i64>,
+) -> Result<(ProblemInfo, ProblemStrInfo)> {
+    let contest = get_contest(
+        config_str_map,
+        contest_arg,
+        contest_type_arg,
+        contest_id_arg,
+    )?;
+    let problem_info = ProblemInfo {
+        contest,
+        problem_id: problem_id.to_lowercase(),
+        task_screen_name: "".into(),
+        time_limit_ms: None,
+        memory_limit_mb: None,
+        score: None,
+    };
+    let problem_str_info = get_problem_str_info(&problem_info);
+    add_task_name_to_problem_info(acn, problem_info, problem_str_info)
+        .await
+        .with_context(|| {
+            "Failed to get task name. Please check you logged in and the contest exists. If the contest has not started yet, try `ac-ninja prepare <contest> --wait`"
+        })
+}
+
+// "abc350"のような英字と数字からなるコンテスト名は, {{contest_type}}と{{contest_id}}にも分ける
+pub fn get_contest_str_info(contest: &str) -> ProblemStrInfo {
+    let contest = contest.to_lowercase();
+    let mut buf: HashMap<String, String> = HashMap::new();
+    let re = Regex::new(r"^([a-z]+)([0-9]+)$").unwrap();
+    if let Some(caps) = re.captures(&contest) {
+        let contest_id: i64 = caps[2].parse().unwrap_or_default();
+        buf.insert("contest_type".to_string(), caps[1].to_string());
+        buf.insert("contest_id".to_string(), contest_id.to_string());
+        buf.insert(
+            "contest_id_0_pad".to_string(),
+            format!("{:0>3}", contest_id),
+        );
     }
+    buf.insert("contest".to_string(), contest);
 
-    let wrong_dir_error: anyhow::Error = anyhow!("If you are not in the directory configured, you need to specify the problem information with options.");
-    Err(wrong_dir_error)
+    buf
 }
 
 fn get_problem_str_info(problem_info: &ProblemInfo) -> HashMap<String, String> {
-    let mut buf: HashMap<String, String> = HashMap::new();
-    buf.insert(
-        "contest_type".to_string(),
-        problem_info.contest_type.to_string(),
-    );
-    buf.insert(
-        "contest_id".to_string(),
-        problem_info.contest_id.to_string(),
-    );
-    buf.insert(
-        "contest_id_0_pad".to_string(),
-        format!("{:0>3}", problem_info.contest_id),
-    );
-    buf.insert(
-        "problem_id".to_string(),
-        problem_info.problem_id.to_string(),
-    );
+    let mut buf = get_contest_str_info(&problem_info.contest);
+    buf.insert("problem_id".to_string(), problem_info.problem_id.clone());
 
     buf
 }
 
-pub fn print_problem_info(problem_str_info: &ProblemStrInfo) -> Result<()> {
+pub fn get_source_file_path(
+    problem_str_info: &ProblemStrInfo,
+    config_str_map: &ConfigStrMap,
+) -> Result<PathBuf> {
+    let mut data_map: HashMap<String, String> = HashMap::new();
+    data_map.extend(config_str_map.iter().map(|(k, v)| (k.clone(), v.clone())));
+    data_map.extend(problem_str_info.iter().map(|(k, v)| (k.clone(), v.clone())));
+    let source_file = str_format(config_str_map["source_file_path"].clone(), &data_map);
+    Ok(PathBuf::from(full(&source_file)?.to_string()))
+}
+
+pub fn print_problem_info(
+    problem_info: &ProblemInfo,
+    problem_str_info: &ProblemStrInfo,
+) -> Result<()> {
     println!("{}", format!("{:-^30}", " Problem Information ").blue());
     let s = str_format(PROBLEM_INFO_PRINT_FORMAT.to_string(), problem_str_info);
     println!("{}", s);
+    if let Some(time_limit_ms) = problem_info.time_limit_ms {
+        println!("{}: {} ms", "Time limit".green(), time_limit_ms);
+    }
+    if let Some(memory_limit_mb) = problem_info.memory_limit_mb {
+        println!("{}: {} MB", "Memory limit".green(), memory_limit_mb);
+    }
+    if let Some(score) = problem_info.score {
+        println!("{}: {}", "Score".green(), score);
+    }
     Ok(())
 }
 
@@ -439,3 +582,99 @@ pub fn make_execute_command(execute_config: HashMap<String, String>) -> Result<S
 
     Ok(command)
 }
+
+pub fn make_checker_command(checker_config: HashMap<String, String>) -> Result<String> {
+    let command_format = checker_config
+        .get("checker_command")
+        .context("Not found checker commnad in your config file.")?
+        .to_string();
+    let command = str_format(command_format, &checker_config);
+
+    Ok(command)
+}
+
+pub fn make_interactor_command(interactor_config: HashMap<String, String>) -> Result<String> {
+    let command_format = interactor_config
+        .get("interactor_command")
+        .context("Not found interactor commnad in your config file.")?
+        .to_string();
+    let command = str_format(command_format, &interactor_config);
+
+    Ok(command)
+}
+
+pub fn make_generator_command(generator_config: HashMap<String, String>) -> Result<String> {
+    let command_format = generator_config
+        .get("generator_command")
+        .context("Not found generator commnad in your config file.")?
+        .to_string();
+    let command = str_format(command_format, &generator_config);
+
+    Ok(command)
+}
+
+pub fn make_brute_command(brute_config: HashMap<String, String>) -> Result<String> {
+    let command_format = brute_config
+        .get("brute_command")
+        .context("Not found brute commnad in your config file.")?
+        .to_string();
+    let command = str_format(command_format, &brute_config);
+
+    Ok(command)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_detect_contest() {
+        let config_dir = "/home/user/AtCoder/{{CONTEST_TYPE}}/{{contest_id_0_pad}}";
+        assert_eq!(
+            Some("abc059".to_string()),
+            detect_contest(config_dir, "/home/user/AtCoder/ABC/059")
+        );
+        assert_eq!(None, detect_contest(config_dir, "/home/user/AtCoder/ABC"));
+
+        let config_dir = "/home/user/AtCoder/{{contest}}";
+        assert_eq!(
+            Some("typical90".to_string()),
+            detect_contest(config_dir, "/home/user/AtCoder/typical90")
+        );
+        assert_eq!(
+            Some("keyence2021".to_string()),
+            detect_contest(config_dir, "/home/user/AtCoder/keyence2021")
+        );
+    }
+
+    #[test]
+    fn test_parse_task_url() {
+        let problem_info =
+            parse_task_url("https://atcoder.jp/contests/abc300/tasks/abc300_d").unwrap();
+        assert_eq!("abc300", problem_info.contest);
+        assert_eq!("d", problem_info.problem_id);
+        assert_eq!("abc300_d", problem_info.task_screen_name);
+
+        let problem_info =
+            parse_task_url("https://atcoder.jp/contests/typical90/tasks/typical90_bx?lang=en")
+                .unwrap();
+        assert_eq!("typical90", problem_info.contest);
+        assert_eq!("bx", problem_info.problem_id);
+        assert_eq!("typical90_bx", problem_info.task_screen_name);
+
+        assert!(parse_task_url("a").is_none());
+    }
+
+    #[test]
+    fn test_get_contest_str_info() {
+        let info = get_contest_str_info("ABC059");
+        assert_eq!("abc059", info["contest"]);
+        assert_eq!("abc", info["contest_type"]);
+        assert_eq!("59", info["contest_id"]);
+        assert_eq!("059", info["contest_id_0_pad"]);
+
+        let info = get_contest_str_info("practice_contest");
+        assert_eq!("practice_contest", info["contest"]);
+        assert_eq!(None, info.get("contest_type"));
+    }
+}
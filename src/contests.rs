@@ -0,0 +1,170 @@
+use anyhow::{Context, Result};
+use colored::*;
+use prettytable::{format, row, Table};
+use scraper::{ElementRef, Html, Selector};
+use time::{format_description, OffsetDateTime, UtcOffset};
+
+use crate::ac_scraper::get_language;
+use crate::data::ACN;
+use crate::util::cell_text;
+
+const CONTESTS_URL: &str = "https://atcoder.jp/contests";
+// 常設中のコンテストは開始時刻に意味がないので表示しない
+const CONTEST_TABLES: [(&str, &str); 3] = [
+    ("#contest-table-action", " Running "),
+    ("#contest-table-upcoming", " Upcoming "),
+    ("#contest-table-recent", " Recent "),
+];
+// 一覧ページの時刻は"2024-04-20 21:00:00+0900"のようにJSTで書かれている
+const START_TIME_FORMAT: &str =
+    "[year]-[month]-[day] [hour]:[minute]:[second][offset_hour sign:mandatory][offset_minute]";
+
+#[derive(Debug, PartialEq, Eq)]
+struct Contest {
+    start_time: OffsetDateTime,
+    slug: String,
+    name: String,
+    duration: String,
+    rated_range: String,
+}
+
+fn parse_contest_table(doc: &Html, table_selector: &str) -> Vec<Contest> {
+    let row_selector = Selector::parse(&format!("{} tbody tr", table_selector)).unwrap();
+    let cell_selector = Selector::parse("td").unwrap();
+    let link_selector = Selector::parse("a[href^='/contests/']").unwrap();
+    let start_time_format = format_description::parse(START_TIME_FORMAT).unwrap();
+
+    doc.select(&row_selector)
+        .filter_map(|row| {
+            let cells: Vec<ElementRef> = row.select(&cell_selector).collect();
+            if cells.len() < 4 {
+                return None;
+            }
+            let start_time =
+                OffsetDateTime::parse(&cell_text(&cells[0]), &start_time_format).ok()?;
+            let link = cells[1].select(&link_selector).next()?;
+            let slug = link.value().attr("href")?.trim_start_matches("/contests/");
+            Some(Contest {
+                start_time,
+                slug: slug.to_string(),
+                name: cell_text(&link),
+                duration: cell_text(&cells[2]),
+                rated_range: cell_text(&cells[3]),
+            })
+        })
+        .collect()
+}
+
+// 夏時間があるので, 時刻ごとにその時点のローカルのオフセットを求める
+// check_samplesのwait4と同じくunix専用で, tm_gmtoffはglibc, musl, macOS, BSDにある
+// time::UtcOffset::local_offset_atはマルチスレッドだとunixでは常に失敗するので使わない
+fn local_offset(time: OffsetDateTime) -> UtcOffset {
+    let timestamp = time.unix_timestamp() as libc::time_t;
+    let gmtoff = unsafe {
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&timestamp, &mut tm).is_null() {
+            return UtcOffset::UTC;
+        }
+        tm.tm_gmtoff
+    };
+    UtcOffset::from_whole_seconds(gmtoff as i32).unwrap_or(UtcOffset::UTC)
+}
+
+fn format_start_time(start_time: OffsetDateTime, offset: UtcOffset) -> String {
+    let local = start_time.to_offset(offset);
+    let (hours, minutes, _) = offset.as_hms();
+    let sign = if offset.is_negative() { '-' } else { '+' };
+    format!(
+        "{}-{:02}-{:02} {:02}:{:02} ({}{:02}:{:02})",
+        local.year(),
+        local.month() as u8,
+        local.day(),
+        local.hour(),
+        local.minute(),
+        sign,
+        hours.abs(),
+        minutes.abs()
+    )
+}
+
+fn print_contests(contests: &[Contest]) {
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_BOX_CHARS);
+    table.set_titles(row!["Start", "Duration", "Rated", "Contest", "Name"]);
+    for contest in contests {
+        table.add_row(row![
+            format_start_time(contest.start_time, local_offset(contest.start_time)),
+            r->contest.duration,
+            contest.rated_range,
+            contest.slug.green(),
+            contest.name
+        ]);
+    }
+    table.printstd();
+}
+
+// 開催中, 予定, 最近のコンテストを表示する. Contestの列は-cにそのまま渡せる
+pub async fn show_contests(acn: &ACN) -> Result<()> {
+    let url = format!(
+        "{}?lang={}",
+        CONTESTS_URL,
+        get_language(&acn.config_str_map)?
+    );
+    let body = acn
+        .client
+        .get(&url)
+        .send()
+        .await?
+        .error_for_status()
+        .with_context(|| format!("Failed to get {}", url))?
+        .text()
+        .await?;
+    let doc = Html::parse_document(&body);
+
+    for (table_selector, title) in CONTEST_TABLES {
+        let contests = parse_contest_table(&doc, table_selector);
+        if contests.is_empty() {
+            continue;
+        }
+        println!("{}", format!("{:-^30}", title).blue());
+        print_contests(&contests);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_contest_table() {
+        let body = r#"<div id="contest-table-upcoming"><table><tbody>
+            <tr>
+                <td class="text-center"><a href="http://www.timeanddate.com/worldclock/fixedtime.html?iso=20240420T2100&p1=248" target="blank"><time class="fixtime fixtime-full">2024-04-20 21:00:00+0900</time></a></td>
+                <td><span aria-hidden="true" data-toggle="tooltip" title="Algorithm">Ⓐ</span> <span class="user-blue">◉</span>
+                    <a href="/contests/abc350">Toyota Programming Contest 2024#4（AtCoder Beginner Contest 350）</a></td>
+                <td class="text-center">01:40</td>
+                <td class="text-center"> - 1999</td>
+            </tr>
+        </tbody></table></div>"#;
+        let doc = Html::parse_document(body);
+        let contests = parse_contest_table(&doc, "#contest-table-upcoming");
+        assert_eq!(1, contests.len());
+        assert_eq!("abc350", contests[0].slug);
+        assert_eq!("01:40", contests[0].duration);
+        assert_eq!("- 1999", contests[0].rated_range);
+        assert_eq!(1713614400, contests[0].start_time.unix_timestamp());
+        assert!(parse_contest_table(&doc, "#contest-table-recent").is_empty());
+
+        let offset = UtcOffset::from_hms(-5, -30, 0).unwrap();
+        assert_eq!(
+            "2024-04-20 06:30 (-05:30)",
+            format_start_time(contests[0].start_time, offset)
+        );
+        assert_eq!(
+            "2024-04-20 12:00 (+00:00)",
+            format_start_time(contests[0].start_time, UtcOffset::UTC)
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use anyhow::{Context, Result};
+use shellexpand::full;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{ConfigStrMap, ProblemStrInfo};
+use crate::util::str_format;
+
+const INPUT_EXTENSION: &str = "in";
+const OUTPUT_EXTENSION: &str = "out";
+
+#[derive(Clone)]
+pub struct TestCase {
+    pub name: String,
+    pub input: String,
+    pub expected: Option<String>,
+}
+
+// configのtest_dirを解決する. 設定されていなければNone
+pub fn get_test_dir(
+    problem_str_info: &ProblemStrInfo,
+    config_str_map: &ConfigStrMap,
+) -> Result<Option<PathBuf>> {
+    let Some(test_dir) = config_str_map.get("test_dir") else {
+        return Ok(None);
+    };
+    let mut data_map: HashMap<String, String> = HashMap::new();
+    data_map.extend(config_str_map.iter().map(|(k, v)| (k.clone(), v.clone())));
+    data_map.extend(problem_str_info.iter().map(|(k, v)| (k.clone(), v.clone())));
+    let test_dir = str_format(test_dir.clone(), &data_map);
+    Ok(Some(PathBuf::from(full(&test_dir)?.to_string())))
+}
+
+// test_dir内の*.inと, 同名の*.outを読み込む
+pub fn load_custom_cases(
+    problem_str_info: &ProblemStrInfo,
+    config_str_map: &ConfigStrMap,
+) -> Result<Vec<TestCase>> {
+    let Some(test_dir) = get_test_dir(problem_str_info, config_str_map)? else {
+        return Ok(vec![]);
+    };
+    if !test_dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut input_paths: Vec<PathBuf> = fs::read_dir(&test_dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == INPUT_EXTENSION))
+        .collect();
+    input_paths.sort();
+
+    let mut cases: Vec<TestCase> = Vec::new();
+    for input_path in input_paths {
+        let input = fs::read_to_string(&input_path)
+            .with_context(|| format!("Failed to read {}", input_path.display()))?;
+        let output_path = input_path.with_extension(OUTPUT_EXTENSION);
+        let expected = if output_path.is_file() {
+            Some(fs::read_to_string(&output_path)?)
+        } else {
+            None
+        };
+        cases.push(TestCase {
+            name: input_path
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string(),
+            input,
+            expected,
+        });
+    }
+
+    Ok(cases)
+}
+
+// 既存のケースと被らない名前で*.inと*.outを保存する
+pub fn save_custom_case(
+    test_dir: &Path,
+    prefix: &str,
+    input: &str,
+    expected: &str,
+) -> Result<PathBuf> {
+    fs::create_dir_all(test_dir)?;
+    let mut n = 1;
+    let input_path = loop {
+        let path = test_dir.join(format!("{}{}.{}", prefix, n, INPUT_EXTENSION));
+        if !path.exists() {
+            break path;
+        }
+        n += 1;
+    };
+    fs::write(&input_path, input)?;
+    fs::write(input_path.with_extension(OUTPUT_EXTENSION), expected)?;
+
+    Ok(input_path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::TempDir;
+
+    #[test]
+    fn test_load_custom_cases() {
+        let test_dir = TempDir::new("custom").unwrap();
+        test_dir.write("big.in", "3\n");
+        test_dir.write("big.out", "6\n");
+        test_dir.write("corner.in", "0\n");
+        test_dir.write("memo.txt", "ignored");
+
+        let mut config_str_map = ConfigStrMap::new();
+        config_str_map.insert(
+            "test_dir".to_string(),
+            format!(
+                "{}/{{{{problem_id}}}}",
+                test_dir.path().parent().unwrap().display()
+            ),
+        );
+        let mut problem_str_info = ProblemStrInfo::new();
+        problem_str_info.insert(
+            "problem_id".to_string(),
+            test_dir
+                .path()
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string(),
+        );
+
+        let cases = load_custom_cases(&problem_str_info, &config_str_map).unwrap();
+        assert_eq!(2, cases.len());
+        assert_eq!("big.in", cases[0].name);
+        assert_eq!(Some("6\n".to_string()), cases[0].expected);
+        assert_eq!("corner.in", cases[1].name);
+        assert_eq!(None, cases[1].expected);
+    }
+}
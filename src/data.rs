@@ -1,5 +1,6 @@
 use crate::{
     ac_scraper::get_local_session,
+    cache::CacheMode,
     config::{get_config, ConfigMap, ConfigStrMap, ToHashMapString},
     GlobalArgs,
 };
@@ -12,6 +13,7 @@ pub struct ACN {
     pub config_str_map: ConfigStrMap,
     pub client: Client,
     pub cookies: Option<HeaderMap>,
+    pub cache_mode: CacheMode,
 }
 
 const USER_AGENT: &str = "ac-ninja";
@@ -26,12 +28,16 @@ impl ACN {
             .build()
             .unwrap();
         let cookies = get_local_session()?;
+        let cache_mode = args.as_ref().map_or(CacheMode::Normal, |args| {
+            CacheMode::from_flags(args.offline, args.refresh)
+        });
         if cookies.is_some() {
             Ok(ACN {
                 config_map,
                 config_str_map,
                 client,
                 cookies,
+                cache_mode,
             })
         } else {
             Ok(ACN {
@@ -39,6 +45,7 @@ impl ACN {
                 config_str_map,
                 client,
                 cookies: None,
+                cache_mode,
             })
         }
     }
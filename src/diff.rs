@@ -0,0 +1,222 @@
+use colored::*;
+
+use crate::check_samples::{is_same_token, FailedDetail};
+
+// 差分の前後に残す一致行の数
+const FOLD_CONTEXT: usize = 2;
+const INPUT_PREVIEW_LINES: usize = 10;
+
+fn split_lines(s: &str) -> Vec<&str> {
+    let s = s.trim_end_matches(['\n', '\r']);
+    if s.is_empty() {
+        return vec![];
+    }
+    s.lines().collect()
+}
+
+// is_acと同じく空白区切りでトークンの位置(バイト)を返す
+fn token_spans(line: &str) -> Vec<(usize, usize)> {
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+    let mut start: Option<usize> = None;
+    for (i, c) in line.char_indices() {
+        if c == ' ' {
+            if let Some(s) = start.take() {
+                spans.push((s, i));
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, line.len()));
+    }
+    spans
+}
+
+fn tokens(line: &str) -> Vec<&str> {
+    token_spans(line)
+        .into_iter()
+        .map(|(s, e)| &line[s..e])
+        .collect()
+}
+
+// 最初に異なるトークンの番号を返す. is_acと同じく誤差epsまでは一致とみなす
+fn first_different_token(expected: &str, output: &str, eps: Option<f64>) -> Option<usize> {
+    let expected = tokens(expected);
+    let output = tokens(output);
+    (0..expected.len().max(output.len())).find(|&i| match (expected.get(i), output.get(i)) {
+        (Some(e), Some(o)) => !is_same_token(e, o, eps),
+        _ => true,
+    })
+}
+
+fn highlight_token(line: &str, token: Option<usize>) -> String {
+    let spans = token_spans(line);
+    match token {
+        Some(token) if token < spans.len() => {
+            let (s, e) = spans[token];
+            format!("{}{}{}", &line[..s], line[s..e].reverse(), &line[e..])
+        }
+        Some(_) => format!("{}{}", line, " ".on_red()),
+        None => line.to_string(),
+    }
+}
+
+fn print_block(title: &str, text: &str) {
+    println!("{}", title.blue());
+    let lines = split_lines(text);
+    for line in lines.iter().take(INPUT_PREVIEW_LINES) {
+        println!("  {}", line);
+    }
+    if lines.len() > INPUT_PREVIEW_LINES {
+        println!(
+            "  {}",
+            format!("... {} more lines", lines.len() - INPUT_PREVIEW_LINES).dimmed()
+        );
+    }
+}
+
+// 一致しない行からFOLD_CONTEXT行以内の行だけを表示する
+fn visible_lines(same: &[bool]) -> Vec<bool> {
+    let mut visible = vec![false; same.len()];
+    for (i, _) in same.iter().enumerate().filter(|(_, s)| !**s) {
+        let from = i.saturating_sub(FOLD_CONTEXT);
+        let to = (i + FOLD_CONTEXT).min(same.len() - 1);
+        for v in visible.iter_mut().take(to + 1).skip(from) {
+            *v = true;
+        }
+    }
+    visible
+}
+
+fn print_line_diff(expected: &str, output: &str, eps: Option<f64>) {
+    let expected_lines = split_lines(expected);
+    let output_lines = split_lines(output);
+    let size = expected_lines.len().max(output_lines.len());
+    let width = size.to_string().len();
+
+    let same: Vec<bool> = (0..size)
+        .map(|i| match (expected_lines.get(i), output_lines.get(i)) {
+            (Some(e), Some(o)) => first_different_token(e, o, eps).is_none(),
+            _ => false,
+        })
+        .collect();
+    let visible = visible_lines(&same);
+
+    if let Some(line) = same.iter().position(|s| !s) {
+        let e = expected_lines.get(line).copied().unwrap_or("");
+        let o = output_lines.get(line).copied().unwrap_or("");
+        let token = first_different_token(e, o, eps).unwrap_or(0);
+        let text = if token < token_spans(o).len() { o } else { e };
+        let column = token_spans(text)
+            .get(token)
+            .map_or(text.chars().count(), |(s, _)| text[..*s].chars().count())
+            + 1;
+        println!(
+            "{}",
+            format!(
+                "First difference at line {}, column {} (token {})",
+                line + 1,
+                column,
+                token + 1
+            )
+            .yellow()
+        );
+    }
+
+    let mut folded = 0;
+    for i in 0..size {
+        if !visible[i] {
+            folded += 1;
+            continue;
+        }
+        if folded > 0 {
+            println!(
+                "{}",
+                format!("{:>width$}   ... {} identical lines", "", folded).dimmed()
+            );
+            folded = 0;
+        }
+        let e = expected_lines.get(i).copied();
+        let o = output_lines.get(i).copied();
+        if same[i] {
+            println!("  {:>width$} │ {}", i + 1, e.unwrap_or(""));
+            continue;
+        }
+        let token = first_different_token(e.unwrap_or(""), o.unwrap_or(""), eps);
+        if let Some(e) = e {
+            println!(
+                "{} {:>width$} │ {}",
+                "-".green(),
+                i + 1,
+                highlight_token(e, token).green()
+            );
+        }
+        if let Some(o) = o {
+            println!(
+                "{} {:>width$} │ {}",
+                "+".red(),
+                i + 1,
+                highlight_token(o, token).red()
+            );
+        }
+    }
+    if folded > 0 {
+        println!(
+            "{}",
+            format!("{:>width$}   ... {} identical lines", "", folded).dimmed()
+        );
+    }
+}
+
+pub fn display_failed_diff(failed_details: Vec<FailedDetail>, eps: Option<f64>) {
+    for detail in failed_details {
+        println!(
+            "{} {}",
+            format!("{:-^30}", format!(" Sample {} ", detail.name)).blue(),
+            detail.status.as_display_string()
+        );
+        if let Some(message) = detail.message {
+            println!("{}", message.yellow());
+        }
+        if !detail.input.is_empty() {
+            print_block("Input", &detail.input);
+        }
+        if !detail.expected.is_empty() || !detail.output.is_empty() {
+            println!(
+                "{} ({} expected, {} output)",
+                "Diff".blue(),
+                "-".green(),
+                "+".red()
+            );
+            print_line_diff(&detail.expected, &detail.output, eps);
+        }
+        if !detail.stderr.is_empty() {
+            print_block("Stderr", &detail.stderr);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_first_different_token() {
+        assert_eq!(None, first_different_token("1 2 3", "1 2 3", None));
+        assert_eq!(None, first_different_token("1 2 3", "1  2 3 ", None));
+        assert_eq!(Some(1), first_different_token("1 2 3", "1 5 3", None));
+        assert_eq!(Some(2), first_different_token("1 2 3", "1 2", None));
+        assert_eq!(Some(0), first_different_token("0.5", "0.5000001", None));
+        assert_eq!(None, first_different_token("0.5", "0.5000001", Some(1e-6)));
+    }
+
+    #[test]
+    fn test_visible_lines() {
+        let same = [true, true, true, true, true, false, true, true, true, true];
+        assert_eq!(
+            vec![false, false, false, true, true, true, true, true, false, false],
+            visible_lines(&same)
+        );
+    }
+}
@@ -0,0 +1,293 @@
+use anyhow::{anyhow, Context, Result};
+use colored::*;
+use prettytable::{format, row, table};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::ac_scraper::Samples;
+use crate::check_samples::{
+    add_total_status_to_table, compile, get_runtime_error, get_time_limit, kill_process_group,
+    FailedDetail, SampleResults, Status,
+};
+use crate::config::{
+    make_execute_command, make_interactor_command, ConfigMap, ConfigStrMap, ConfigValue,
+    ProblemStrInfo,
+};
+use crate::util::split_one_line_command;
+
+// 解答 -> インタラクタ の向きがtrue
+type ConversationLog = Arc<Mutex<Vec<(bool, String)>>>;
+
+// 制限時間を超えたときに孫プロセスまでkillできるよう, 別のプロセスグループで起動する
+fn spawn_piped(command: &str, args: &[&str]) -> Result<Child> {
+    Command::new(command)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0)
+        .spawn()
+        .with_context(|| format!("Failed to run {}", command))
+}
+
+// 一行ずつ転送しながら会話を記録する
+fn relay(
+    from: impl Read + Send + 'static,
+    mut to: impl Write + Send + 'static,
+    log: ConversationLog,
+    from_solution: bool,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(from);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match reader.read_line(&mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    log.lock()
+                        .unwrap()
+                        .push((from_solution, line.trim_end().to_string()));
+                    if to.write_all(line.as_bytes()).is_err() || to.flush().is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
+
+fn read_all(from: impl Read + Send + 'static) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut reader = from;
+        let mut buf: Vec<u8> = Vec::new();
+        let _ = reader.read_to_end(&mut buf);
+        String::from_utf8_lossy(&buf).to_string()
+    })
+}
+
+fn print_conversation(log: &ConversationLog) {
+    println!("{}", format!("{:-^30}", " Conversation ").blue());
+    for (from_solution, line) in log.lock().unwrap().iter() {
+        if *from_solution {
+            println!("{} {}", ">".green(), line);
+        } else {
+            println!("{} {}", "<".yellow(), line);
+        }
+    }
+}
+
+pub fn interactive_check(
+    problem_str_info: &ProblemStrInfo,
+    samples: &Samples,
+    config_str_map: &ConfigStrMap,
+    config_map: &ConfigMap,
+) -> Result<SampleResults> {
+    let mut table = table!([c => "Sample", "Status"]);
+    table.set_format(*format::consts::FORMAT_BOX_CHARS);
+
+    let mut execute_config: HashMap<String, String> = HashMap::new();
+    execute_config.extend(config_str_map.iter().map(|(k, v)| (k.clone(), v.clone())));
+    execute_config.extend(problem_str_info.iter().map(|(k, v)| (k.clone(), v.clone())));
+    if !execute_config.contains_key("interactor_command") {
+        return Err(anyhow!(
+            "This is an interactive problem. Please set interactor_command in your config file."
+        ));
+    }
+
+    let compile_status = if let ConfigValue::Boolean(true) = config_map
+        .get("need_to_compile")
+        .unwrap_or(&ConfigValue::Boolean(false))
+    {
+        compile(problem_str_info, config_str_map)?
+    } else {
+        Status::AC
+    };
+
+    println!("{}", format!("{:-^30}", " Interactive ").blue());
+    if compile_status != Status::AC {
+        table = add_total_status_to_table(table, &compile_status);
+        table.printstd();
+        return Ok(SampleResults {
+            total_status: compile_status,
+            failed_details: vec![],
+        });
+    }
+
+    let execute_command = make_execute_command(execute_config.clone())?;
+    let (command, args) = split_one_line_command(&execute_command);
+    let interactor_command = make_interactor_command(execute_config)?;
+    let (interactor, interactor_args) = split_one_line_command(&interactor_command);
+    let time_limit = get_time_limit(samples.time_limit_ms, config_map);
+
+    println!("{}: {}", "Execute command".green(), command);
+    println!("{}: {:?}", "Execute arguments".green(), args);
+    println!("{}: {}", "Interactor command".green(), interactor);
+    println!("{}: {:?}", "Interactor arguments".green(), interactor_args);
+    println!("{}: {} ms", "Time limit".green(), time_limit.as_millis());
+
+    let mut solution_child = spawn_piped(command, &args)?;
+    let mut interactor_child = spawn_piped(interactor, &interactor_args)?;
+    let log: ConversationLog = Arc::new(Mutex::new(Vec::new()));
+    let to_interactor = relay(
+        solution_child.stdout.take().unwrap(),
+        interactor_child.stdin.take().unwrap(),
+        log.clone(),
+        true,
+    );
+    let to_solution = relay(
+        interactor_child.stdout.take().unwrap(),
+        solution_child.stdin.take().unwrap(),
+        log.clone(),
+        false,
+    );
+    let solution_stderr = read_all(solution_child.stderr.take().unwrap());
+    let interactor_stderr = read_all(interactor_child.stderr.take().unwrap());
+
+    let start = Instant::now();
+    let mut solution_status = None;
+    let mut interactor_status = None;
+    let timed_out = loop {
+        if solution_status.is_none() {
+            solution_status = solution_child.try_wait()?;
+        }
+        if interactor_status.is_none() {
+            interactor_status = interactor_child.try_wait()?;
+        }
+        if solution_status.is_some() && interactor_status.is_some() {
+            break false;
+        }
+        if start.elapsed() >= time_limit {
+            kill_process_group(solution_child.id());
+            kill_process_group(interactor_child.id());
+            solution_child.wait()?;
+            interactor_child.wait()?;
+            break true;
+        }
+        thread::sleep(Duration::from_millis(5));
+    };
+    let _ = to_interactor.join();
+    let _ = to_solution.join();
+    let stderr = solution_stderr.join().expect("Failed to read stderr!");
+    let interactor_message = interactor_stderr.join().expect("Failed to read stderr!");
+
+    print_conversation(&log);
+
+    let runtime_error = solution_status.as_ref().and_then(get_runtime_error);
+    let status = if timed_out {
+        Status::TLE
+    } else if runtime_error.is_some() {
+        Status::RE
+    } else if interactor_status.is_some_and(|s| s.success()) {
+        Status::AC
+    } else {
+        Status::WA
+    };
+    let message = match runtime_error {
+        Some(runtime_error) => Some(runtime_error),
+        None if !interactor_message.trim().is_empty() => {
+            Some(interactor_message.trim().to_string())
+        }
+        None => None,
+    };
+
+    table.add_row(row![c => "Interactive", status.as_display_string()]);
+    table = add_total_status_to_table(table, &status);
+    table.printstd();
+
+    let failed_details = if status == Status::AC {
+        vec![]
+    } else {
+        vec![FailedDetail {
+            name: "Interactive".to_string(),
+            status,
+            message,
+            input: "".to_string(),
+            expected: "".to_string(),
+            output: "".to_string(),
+            stderr,
+        }]
+    };
+
+    Ok(SampleResults {
+        total_status: status,
+        failed_details,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::TempDir;
+
+    #[test]
+    fn test_interactive_check() {
+        let work_dir = TempDir::new("interactive").unwrap();
+        let solution = work_dir.write("solution.sh", "read x\necho $((x * 2))\n");
+        let interactor = work_dir.write("interactor.sh", "echo 21\nread y\n[ \"$y\" = 42 ]\n");
+
+        let samples = Samples {
+            inputs: vec![],
+            outputs: vec![],
+            size: 0,
+            time_limit_ms: Some(5000),
+            memory_limit_mb: None,
+            score: None,
+            eps: None,
+            is_interactive: true,
+            statements: Default::default(),
+            custom_cases: vec![],
+        };
+        let mut config_map = ConfigMap::new();
+        config_map.insert("need_to_compile".to_string(), ConfigValue::Boolean(false));
+        let mut config_str_map = ConfigStrMap::new();
+        config_str_map.insert(
+            "execute_command".to_string(),
+            format!("sh {}", solution.to_str().unwrap()),
+        );
+        config_str_map.insert(
+            "interactor_command".to_string(),
+            format!("sh {}", interactor.to_str().unwrap()),
+        );
+        let results = interactive_check(
+            &ProblemStrInfo::new(),
+            &samples,
+            &config_str_map,
+            &config_map,
+        )
+        .unwrap();
+        assert!(results.total_status == Status::AC);
+
+        work_dir.write("solution.sh", "read x\necho $((x * 3))\n");
+        let results = interactive_check(
+            &ProblemStrInfo::new(),
+            &samples,
+            &config_str_map,
+            &config_map,
+        )
+        .unwrap();
+        assert!(results.total_status == Status::WA);
+
+        // 孫プロセスのsleepが残ってもプロセスグループごとkillされて終わる
+        work_dir.write("solution.sh", "sleep 100; echo 1\n");
+        let samples = Samples {
+            time_limit_ms: Some(300),
+            ..samples
+        };
+        let start = Instant::now();
+        let results = interactive_check(
+            &ProblemStrInfo::new(),
+            &samples,
+            &config_str_map,
+            &config_map,
+        )
+        .unwrap();
+        assert!(results.total_status == Status::TLE);
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+}
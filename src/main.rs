@@ -1,11 +1,22 @@
 mod ac_scraper;
+mod cache;
 mod check_samples;
 mod config;
+mod contests;
+mod custom_cases;
 mod data;
+mod diff;
+mod interactive;
+mod prepare;
+mod shrink;
+mod standings;
+mod statement;
+mod stress;
+mod submissions;
 mod util;
+mod watch;
 
 use std::{
-    collections::HashMap,
     fs,
     io::Write,
     path::PathBuf,
@@ -14,13 +25,23 @@ use std::{
 
 use ac_scraper::*;
 use anyhow::Result;
+use cache::CacheMode;
 use check_samples::*;
-use clap::{Args, Parser, Subcommand, ValueEnum};
+use clap::{Args, Parser, Subcommand};
 use colored::*;
 use config::*;
+use contests::show_contests;
+use custom_cases::load_custom_cases;
 use data::*;
-use shellexpand::full;
-use util::str_format;
+use diff::display_failed_diff;
+use interactive::interactive_check;
+use prepare::prepare_contest;
+use shrink::shrink_file;
+use standings::show_standings;
+use statement::show_statement;
+use stress::stress_test;
+use submissions::{show_submissions, sync_submissions};
+use watch::watch_source_file;
 
 #[derive(Debug, Parser)]
 #[command(
@@ -53,70 +74,95 @@ pub struct GlobalArgs {
     #[arg(short, long)]
     pub insert: bool,
 
-    /// (Required)
+    /// Show failed samples side by side in a table instead of a diff
+    #[arg(long)]
+    pub table: bool,
+
+    /// Re-run samples every time the source file is saved
+    #[arg(short, long)]
+    pub watch: bool,
+
+    /// Use only cached task lists and samples without accessing the network
+    #[arg(long, conflicts_with = "refresh")]
+    pub offline: bool,
+
+    /// Fetch task lists and samples again even if they are cached
+    #[arg(long)]
+    pub refresh: bool,
+
+    /// Run with the interactor (interactive problems are detected automatically)
+    #[arg(long)]
+    pub interactive: bool,
+
+    /// (Required) Task label such as a, ex or a1 [case-insensitive], or the task URL
     #[arg(name = "PROBLEM_ID")]
-    pub problem_id_arg: ProblemIdArg,
+    pub problem_id_arg: String,
 
     /// (Optional) Source file [If you specify source_file, ac-ninja will use the given value to override your config.]
     #[arg(name = "SOURCE_FILE")]
     pub source_file: Option<PathBuf>,
 
-    /// If you're not in configured directory as {{contest_dir}}, you need to specify
-    /// contest_type and contest_id.
-    #[clap(value_enum, long = "type", short = 't', name = "CONTEST_TYPE")]
-    pub contest_type_arg: Option<ContestTypeArg>,
-
-    /// [possible values: 1, 2, 3, ... ]
-    #[arg(long = "id", short = 'I', name = "CONTEST_ID")]
-    pub contest_id_arg: Option<i64>,
+    #[command(flatten)]
+    pub contest: ContestArgs,
 
     /// [possible values: 1, 2, 3, ... ]
     #[arg(long = "sample", short = 's', name = "SAMPLE_CASE_ID")]
     pub sample_case_id_arg: Option<usize>,
+
+    /// Accept numeric outputs within this absolute or relative error [default: detected from the problem statement]
+    #[arg(long = "eps", name = "EPS")]
+    pub eps: Option<f64>,
 }
 
-#[derive(Debug, Clone, ValueEnum)]
-pub enum ContestTypeArg {
-    Abc,
-    Arc,
-    Agc,
+#[derive(Debug, Args)]
+pub struct ShowArgs {
+    /// (Required) Task label such as a, ex or a1 [case-insensitive], or the task URL
+    #[arg(name = "PROBLEM_ID")]
+    pub problem_id_arg: String,
+
+    #[command(flatten)]
+    pub contest: ContestArgs,
+
+    /// Show only this section [statement, constraints, input, output, samples or a heading]
+    #[arg(long = "section", short = 's', name = "SECTION")]
+    pub section: Option<String>,
 }
 
-#[derive(Debug, Clone, ValueEnum)]
-pub enum ProblemIdArg {
-    A,
-    B,
-    C,
-    D,
-    E,
-    F,
-    G,
-    H,
+#[derive(Debug, Args)]
+pub struct ContestArgs {
+    /// If you're not in configured directory as {{contest_dir}}, you need to specify
+    /// the contest such as abc350, typical90 or practice2.
+    #[arg(long = "contest", short = 'c', name = "CONTEST")]
+    pub contest_arg: Option<String>,
+
+    /// Or specify contest_type and contest_id such as abc and 350.
+    #[arg(long = "type", short = 't', name = "CONTEST_TYPE")]
+    pub contest_type_arg: Option<String>,
+
+    /// [possible values: 1, 2, 3, ... ]
+    #[arg(long = "id", short = 'I', name = "CONTEST_ID")]
+    pub contest_id_arg: Option<i64>,
 }
 
-impl ProblemIdArg {
-    pub fn as_char(&self) -> char {
-        match &self {
-            ProblemIdArg::A => 'a',
-            ProblemIdArg::B => 'b',
-            ProblemIdArg::C => 'c',
-            ProblemIdArg::D => 'd',
-            ProblemIdArg::E => 'e',
-            ProblemIdArg::F => 'f',
-            ProblemIdArg::G => 'g',
-            ProblemIdArg::H => 'h',
-        }
-    }
+#[derive(Debug, Args)]
+pub struct SubmissionsArgs {
+    /// (Optional) Show only submissions to this task [label or task URL]
+    #[arg(name = "PROBLEM_ID")]
+    pub problem_id_arg: Option<String>,
+
+    #[command(flatten)]
+    pub contest: ContestArgs,
 }
 
-impl ContestTypeArg {
-    pub fn as_str(&self) -> String {
-        match &self {
-            ContestTypeArg::Abc => "abc".to_string(),
-            ContestTypeArg::Arc => "arc".to_string(),
-            ContestTypeArg::Agc => "agc".to_string(),
-        }
-    }
+#[derive(Debug, Args)]
+pub struct PrepareArgs {
+    /// (Required) Contest such as abc350, typical90 or practice2
+    #[arg(name = "CONTEST")]
+    pub contest: String,
+
+    /// Show a countdown and fetch all samples in parallel when the contest starts
+    #[arg(short, long)]
+    pub wait: bool,
 }
 
 #[derive(Debug, Subcommand)]
@@ -127,6 +173,62 @@ enum MiniCommand {
     Logout,
     /// Check if local session cookie is valid
     LoginCheck,
+    /// Compare your solution with brute_command on inputs from generator_command
+    Stress(StressArgs),
+    /// Shrink a failing input while your solution and brute_command still disagree
+    Shrink(ShrinkArgs),
+    /// Create source files for every problem in a contest and fetch all samples
+    Prepare(PrepareArgs),
+    /// Show the problem statement in the terminal
+    Show(ShowArgs),
+    /// Show your submissions and wait until pending judgements finish
+    Submissions(SubmissionsArgs),
+    /// Show the standings of you and your friends
+    Standings(ContestArgs),
+    /// List running, upcoming and recent contests
+    Contests,
+    /// Save the sources of your accepted submissions to archive_path or source_file_path
+    SyncSubmissions(ContestArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct StressArgs {
+    /// (Required) Task label such as a, ex or a1 [case-insensitive], or the task URL
+    #[arg(name = "PROBLEM_ID")]
+    pub problem_id_arg: String,
+
+    #[command(flatten)]
+    pub contest: ContestArgs,
+
+    /// Number of random tests
+    #[arg(long = "count", short = 'n', name = "COUNT", default_value_t = 1000)]
+    pub count: usize,
+
+    /// Accept numeric outputs within this absolute or relative error
+    #[arg(long = "eps", name = "EPS")]
+    pub eps: Option<f64>,
+
+    /// Shrink the counterexample before saving it
+    #[arg(long)]
+    pub shrink: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ShrinkArgs {
+    /// (Required) Task label such as a, ex or a1 [case-insensitive], or the task URL
+    #[arg(name = "PROBLEM_ID")]
+    pub problem_id_arg: String,
+
+    /// (Required) Failing input file [The result is saved as <INPUT_FILE>_min.in and _min.out]
+    #[arg(name = "INPUT_FILE")]
+    pub input_file: PathBuf,
+
+    #[command(flatten)]
+    pub contest: ContestArgs,
+
+    /// Accept numeric outputs within this absolute or relative error
+    #[arg(long = "eps", name = "EPS")]
+    pub eps: Option<f64>,
 }
 
 #[tokio::main]
@@ -156,11 +258,132 @@ async fn main() -> Result<()> {
                 }
                 return Ok(());
             }
+            MiniCommand::Stress(args) => {
+                let (problem_info, problem_str_info) = get_problem_info(
+                    &acn,
+                    &acn.config_str_map,
+                    &args.problem_id_arg,
+                    &args.contest.contest_arg,
+                    &args.contest.contest_type_arg,
+                    args.contest.contest_id_arg,
+                )
+                .await?;
+                let samples = get_sample_cases(&problem_str_info, &acn, None).await?;
+                let (problem_info, problem_str_info) =
+                    add_limits_to_problem_info(&samples, problem_info, problem_str_info);
+                print_problem_info(&problem_info, &problem_str_info)?;
+                stress_test(
+                    &problem_str_info,
+                    &acn.config_str_map,
+                    &acn.config_map,
+                    problem_info.time_limit_ms,
+                    args.count,
+                    args.eps,
+                    args.shrink,
+                )?;
+                return Ok(());
+            }
+            MiniCommand::Shrink(args) => {
+                let (problem_info, problem_str_info) = get_problem_info(
+                    &acn,
+                    &acn.config_str_map,
+                    &args.problem_id_arg,
+                    &args.contest.contest_arg,
+                    &args.contest.contest_type_arg,
+                    args.contest.contest_id_arg,
+                )
+                .await?;
+                let samples = get_sample_cases(&problem_str_info, &acn, None).await?;
+                let (problem_info, problem_str_info) =
+                    add_limits_to_problem_info(&samples, problem_info, problem_str_info);
+                print_problem_info(&problem_info, &problem_str_info)?;
+                shrink_file(
+                    &problem_str_info,
+                    &acn.config_str_map,
+                    &acn.config_map,
+                    &args.input_file,
+                    problem_info.time_limit_ms,
+                    args.eps,
+                )?;
+                return Ok(());
+            }
+            MiniCommand::Show(args) => {
+                let (problem_info, problem_str_info) = get_problem_info(
+                    &acn,
+                    &acn.config_str_map,
+                    &args.problem_id_arg,
+                    &args.contest.contest_arg,
+                    &args.contest.contest_type_arg,
+                    args.contest.contest_id_arg,
+                )
+                .await?;
+                let samples = get_sample_cases(&problem_str_info, &acn, None).await?;
+                let (problem_info, problem_str_info) =
+                    add_limits_to_problem_info(&samples, problem_info, problem_str_info);
+                print_problem_info(&problem_info, &problem_str_info)?;
+                show_statement(&samples, &acn.config_str_map, &args.section)?;
+                return Ok(());
+            }
+            MiniCommand::Submissions(args) => {
+                let (contest_str_info, task_screen_name) = match &args.problem_id_arg {
+                    Some(problem_id) => {
+                        let (problem_info, problem_str_info) = get_problem_info(
+                            &acn,
+                            &acn.config_str_map,
+                            problem_id,
+                            &args.contest.contest_arg,
+                            &args.contest.contest_type_arg,
+                            args.contest.contest_id_arg,
+                        )
+                        .await?;
+                        (problem_str_info, Some(problem_info.task_screen_name))
+                    }
+                    None => {
+                        let contest = get_contest(
+                            &acn.config_str_map,
+                            &args.contest.contest_arg,
+                            &args.contest.contest_type_arg,
+                            args.contest.contest_id_arg,
+                        )?;
+                        (get_contest_str_info(&contest), None)
+                    }
+                };
+                show_submissions(&acn, &contest_str_info, task_screen_name.as_deref()).await?;
+                return Ok(());
+            }
+            MiniCommand::Standings(args) => {
+                let contest = get_contest(
+                    &acn.config_str_map,
+                    &args.contest_arg,
+                    &args.contest_type_arg,
+                    args.contest_id_arg,
+                )?;
+                show_standings(&acn, &get_contest_str_info(&contest)).await?;
+                return Ok(());
+            }
+            MiniCommand::SyncSubmissions(args) => {
+                let contest = get_contest(
+                    &acn.config_str_map,
+                    &args.contest_arg,
+                    &args.contest_type_arg,
+                    args.contest_id_arg,
+                )?;
+                sync_submissions(&acn, &get_contest_str_info(&contest)).await?;
+                return Ok(());
+            }
+            MiniCommand::Contests => {
+                show_contests(&acn).await?;
+                return Ok(());
+            }
+            MiniCommand::Prepare(args) => {
+                prepare_contest(&acn, &args.contest, args.wait).await?;
+                return Ok(());
+            }
         }
     }
 
     // login check
-    if acn.cookies.is_none() {
+    if acn.cookies.is_none() && acn.cache_mode != CacheMode::Offline {
         println!(
             "{}{}",
             "Local session not found!\n".red(),
@@ -172,31 +395,47 @@ async fn main() -> Result<()> {
 
     let cli_args = cli.args.unwrap();
 
-    let (_, problem_str_info) = get_problem_info_from_path(
+    let (problem_info, problem_str_info) = get_problem_info(
         &acn,
         &acn.config_str_map,
-        cli_args.problem_id_arg.as_char(),
-        &cli_args,
+        &cli_args.problem_id_arg,
+        &cli_args.contest.contest_arg,
+        &cli_args.contest.contest_type_arg,
+        cli_args.contest.contest_id_arg,
     )
     .await?;
 
-    print_problem_info(&problem_str_info)?;
-
+    // 手動入力ではサンプルも制限も使わないので, 取得せずに実行する
     if cli_args.insert {
+        print_problem_info(&problem_info, &problem_str_info)?;
         execute_with_manual_input(&problem_str_info, &acn.config_str_map)?;
         return Ok(());
     }
-    let samples = get_sample_cases(&problem_str_info, &acn, cli_args.sample_case_id_arg).await?;
-    let sample_results = sample_check(
-        &problem_str_info,
-        &samples,
-        &acn.config_str_map,
-        &acn.config_map,
-    )?;
-    if !sample_results.failed_details.is_empty() {
-        display_failed_detail(sample_results.failed_details);
+    let mut samples =
+        get_sample_cases(&problem_str_info, &acn, cli_args.sample_case_id_arg).await?;
+    let (problem_info, problem_str_info) =
+        add_limits_to_problem_info(&samples, problem_info, problem_str_info);
+    print_problem_info(&problem_info, &problem_str_info)?;
+
+    if cli_args.eps.is_some() {
+        samples.eps = cli_args.eps;
+    }
+    if cli_args.sample_case_id_arg.is_none() {
+        samples.custom_cases = load_custom_cases(&problem_str_info, &acn.config_str_map)?;
+    }
+
+    if cli_args.watch {
+        let source_path = get_source_file_path(&problem_str_info, &acn.config_str_map)?;
+        watch_source_file(&source_path, || {
+            print_problem_info(&problem_info, &problem_str_info)?;
+            run_check(&problem_str_info, &samples, &acn, &cli_args)?;
+            Ok(())
+        })?;
+        return Ok(());
     }
 
+    let sample_results = run_check(&problem_str_info, &samples, &acn, &cli_args)?;
+
     let should_copy = (sample_results.total_status == Status::AC || cli_args.force)
         && !cli_args.local
         && cli_args.sample_case_id_arg.is_none();
@@ -211,15 +450,44 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+// コンパイルしてサンプルを実行し, 失敗したケースを表示する
+fn run_check(
+    problem_str_info: &ProblemStrInfo,
+    samples: &Samples,
+    acn: &ACN,
+    cli_args: &GlobalArgs,
+) -> Result<SampleResults> {
+    let mut sample_results = if samples.is_interactive || cli_args.interactive {
+        interactive_check(
+            problem_str_info,
+            samples,
+            &acn.config_str_map,
+            &acn.config_map,
+        )?
+    } else {
+        sample_check(
+            problem_str_info,
+            samples,
+            &acn.config_str_map,
+            &acn.config_map,
+        )?
+    };
+    if !sample_results.failed_details.is_empty() {
+        let failed_details = std::mem::take(&mut sample_results.failed_details);
+        if cli_args.table {
+            display_failed_detail(failed_details);
+        } else {
+            display_failed_diff(failed_details, samples.eps);
+        }
+    }
+    Ok(sample_results)
+}
+
 fn copy_source_to_clipboard(
     problem_str_info: &ProblemStrInfo,
     config_str_map: &ConfigStrMap,
 ) -> Result<()> {
-    let mut data_map: HashMap<String, String> = HashMap::new();
-    data_map.extend(config_str_map.iter().map(|(k, v)| (k.clone(), v.clone())));
-    data_map.extend(problem_str_info.iter().map(|(k, v)| (k.clone(), v.clone())));
-    let source_file = str_format(config_str_map["source_file_path"].clone(), &data_map);
-    let source_path = full(&source_file)?.to_string();
+    let source_path = get_source_file_path(problem_str_info, config_str_map)?;
     let source = fs::read(&source_path)?;
 
     let mut child = Command::new("pbcopy").stdin(Stdio::piped()).spawn()?;
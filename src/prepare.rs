@@ -0,0 +1,180 @@
+use anyhow::{anyhow, Context, Result};
+use colored::*;
+use futures_util::future::join_all;
+use shellexpand::full;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+use time::OffsetDateTime;
+use tokio::time::sleep;
+
+use crate::ac_scraper::{get_contest_start_time, get_sample_cases, get_task_list, Task};
+use crate::config::{get_contest_str_info, ProblemStrInfo};
+use crate::data::ACN;
+use crate::util::str_format;
+
+const START_DELAY_MIN: Duration = Duration::from_millis(1000);
+const START_DELAY_RANGE_MS: u64 = 2000;
+const START_RETRY: usize = 10;
+
+pub fn resolve_path(
+    template: &str,
+    problem_str_info: &ProblemStrInfo,
+    acn: &ACN,
+) -> Result<PathBuf> {
+    let mut data_map: HashMap<String, String> = HashMap::new();
+    data_map.extend(
+        acn.config_str_map
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone())),
+    );
+    data_map.extend(problem_str_info.iter().map(|(k, v)| (k.clone(), v.clone())));
+    let path = str_format(template.to_string(), &data_map);
+    // {{contest_type}}などを使うテンプレートは, そう分けられないコンテストには使えない
+    if path.contains("{{") {
+        return Err(anyhow!(
+            "Couldn't resolve {} for this contest. Please use {{{{contest}}}} in your config.",
+            path
+        ));
+    }
+    Ok(PathBuf::from(full(&path)?.to_string()))
+}
+
+fn read_source_template(contest_str_info: &ProblemStrInfo, acn: &ACN) -> Result<String> {
+    let Some(template) = acn.config_str_map.get("source_template") else {
+        return Ok(String::new());
+    };
+    let path = resolve_path(template, contest_str_info, acn)?;
+    fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read source template {}", path.display()))
+}
+
+// 開始直後にアクセスが集中しないよう, 少しずらす
+fn random_delay() -> Duration {
+    let random = RandomState::new().build_hasher().finish();
+    START_DELAY_MIN + Duration::from_millis(random % START_DELAY_RANGE_MS)
+}
+
+fn format_remaining(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    let (days, secs) = (secs / 86400, secs % 86400);
+    let hms = format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{}d {}", days, hms)
+    } else {
+        hms
+    }
+}
+
+async fn wait_for_contest_start(acn: &ACN, contest_str_info: &ProblemStrInfo) -> Result<()> {
+    let start_time = get_contest_start_time(acn, contest_str_info).await?;
+    loop {
+        let remaining = start_time - OffsetDateTime::now_utc();
+        if !remaining.is_positive() {
+            break;
+        }
+        let remaining = Duration::try_from(remaining)?;
+        print!(
+            "\r{}",
+            format!("Contest starts in {}   ", format_remaining(remaining)).green()
+        );
+        std::io::stdout().flush()?;
+        sleep(remaining.min(Duration::from_secs(1))).await;
+    }
+    let delay = random_delay();
+    println!();
+    println!(
+        "{}",
+        format!("Contest started. Fetching in {} ms...", delay.as_millis()).green()
+    );
+    sleep(delay).await;
+    Ok(())
+}
+
+// 開始直後は問題一覧がまだ見えないことがあるので, 何度か取り直す
+async fn get_task_list_with_retry(
+    acn: &ACN,
+    contest_str_info: &ProblemStrInfo,
+    retry: usize,
+) -> Result<Vec<Task>> {
+    let mut attempt = 0;
+    loop {
+        match get_task_list(acn, contest_str_info).await {
+            Ok(tasks) => return Ok(tasks),
+            Err(e) if attempt >= retry => return Err(e),
+            Err(_) => {
+                attempt += 1;
+                sleep(random_delay()).await;
+            }
+        }
+    }
+}
+
+// 問題ごとにソースファイルを作り, サンプルを並列に取得してキャッシュしておく
+pub async fn prepare_contest(acn: &ACN, contest: &str, wait: bool) -> Result<()> {
+    let contest_str_info = get_contest_str_info(contest);
+    let contest_dir = resolve_path(&acn.config_str_map["contest_dir"], &contest_str_info, acn)?;
+    fs::create_dir_all(&contest_dir)?;
+    println!("{}", format!("{:-^30}", " Prepare ").blue());
+    println!("{}: {}", "Contest dir".green(), contest_dir.display());
+
+    let template = read_source_template(&contest_str_info, acn)?;
+    if wait {
+        wait_for_contest_start(acn, &contest_str_info).await?;
+    }
+    let retry = if wait { START_RETRY } else { 0 };
+    let tasks = get_task_list_with_retry(acn, &contest_str_info, retry)
+        .await
+        .context(
+            "Failed to get the task list. Please check you logged in and the contest exists",
+        )?;
+
+    let mut problems: Vec<(String, ProblemStrInfo, PathBuf, bool)> = Vec::new();
+    for task in tasks {
+        let mut problem_str_info = contest_str_info.clone();
+        problem_str_info.insert("problem_id".to_string(), task.label.to_lowercase());
+        problem_str_info.insert("task_screen_name".to_string(), task.task_screen_name);
+
+        let source_path = resolve_path(
+            &acn.config_str_map["source_file_path"],
+            &problem_str_info,
+            acn,
+        )?;
+        let created = if source_path.exists() {
+            false
+        } else {
+            if let Some(dir) = source_path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(&source_path, &template)?;
+            true
+        };
+        problems.push((task.label, problem_str_info, source_path, created));
+    }
+
+    let all_samples = join_all(
+        problems
+            .iter()
+            .map(|(_, problem_str_info, _, _)| get_sample_cases(problem_str_info, acn, None)),
+    )
+    .await;
+    for ((label, _, source_path, created), samples) in problems.iter().zip(all_samples) {
+        let samples = match samples {
+            Ok(samples) => format!("{} samples", samples.size),
+            Err(e) => format!("{} {:#}", "Failed to get samples:".red(), e),
+        };
+        println!(
+            "{:<3} {} ({}){}",
+            label,
+            source_path.display(),
+            samples,
+            if *created { "" } else { " already exists" }
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,247 @@
+use anyhow::{Context, Result};
+use colored::*;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::check_samples::{compile, get_time_limit, Status};
+use crate::config::{
+    make_brute_command, make_execute_command, ConfigMap, ConfigStrMap, ConfigValue, ProblemStrInfo,
+};
+use crate::stress::compare_with_brute;
+
+type Lines = Vec<Vec<String>>;
+
+// "N" の後にN行, または "N" の後にN個の値が1行で続く形式
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Layout {
+    Rows { count_token: usize },
+    Array { count_token: usize },
+    Plain,
+}
+
+fn parse_lines(input: &str) -> Lines {
+    input
+        .lines()
+        .map(|line| line.split_whitespace().map(|s| s.to_string()).collect())
+        .filter(|tokens: &Vec<String>| !tokens.is_empty())
+        .collect()
+}
+
+fn render(lines: &Lines) -> String {
+    lines
+        .iter()
+        .map(|tokens| format!("{}\n", tokens.join(" ")))
+        .collect()
+}
+
+// 1行目の数がどの部分の個数を表しているかを推定する
+fn detect_layout(lines: &Lines) -> Layout {
+    if lines.len() < 2 {
+        return Layout::Plain;
+    }
+    let values: Vec<Option<usize>> = lines[0].iter().map(|t| t.parse().ok()).collect();
+    let find = |n: usize| values.iter().position(|v| *v == Some(n));
+    if lines.len() > 2 {
+        if let Some(count_token) = find(lines.len() - 1) {
+            return Layout::Rows { count_token };
+        }
+    }
+    if let Some(count_token) = find(lines[1].len()) {
+        return Layout::Array { count_token };
+    }
+    Layout::Plain
+}
+
+fn item_count(lines: &Lines, layout: Layout) -> usize {
+    match layout {
+        Layout::Rows { .. } => lines.len() - 1,
+        Layout::Array { .. } => lines[1].len(),
+        Layout::Plain => lines.len(),
+    }
+}
+
+// [from, to)の要素を取り除き, 個数の行も合わせて書き換える
+fn remove_items(lines: &Lines, layout: Layout, from: usize, to: usize) -> Lines {
+    let mut lines = lines.clone();
+    match layout {
+        Layout::Rows { count_token } => {
+            lines.drain(from + 1..to + 1);
+            lines[0][count_token] = (lines.len() - 1).to_string();
+        }
+        Layout::Array { count_token } => {
+            lines[1].drain(from..to);
+            lines[0][count_token] = lines[1].len().to_string();
+        }
+        Layout::Plain => {
+            lines.drain(from..to);
+        }
+    }
+    lines
+}
+
+fn remove_tokens(lines: &Lines, line: usize, from: usize, to: usize) -> Lines {
+    let mut lines = lines.clone();
+    lines[line].drain(from..to);
+    lines
+}
+
+// 失敗し続ける限り, 大きな塊から順に取り除いていく
+fn shrink_chunks(
+    mut lines: Lines,
+    size: impl Fn(&Lines) -> usize,
+    remove: impl Fn(&Lines, usize, usize) -> Lines,
+    is_failing: &mut impl FnMut(&str) -> bool,
+) -> Lines {
+    let mut chunk = size(&lines) / 2;
+    while chunk >= 1 {
+        let mut from = 0;
+        while from < size(&lines) && size(&lines) > 1 {
+            let to = (from + chunk).min(size(&lines));
+            if to - from >= size(&lines) {
+                break;
+            }
+            let candidate = remove(&lines, from, to);
+            if is_failing(&render(&candidate)) {
+                lines = candidate;
+                print!(
+                    "\r{}",
+                    format!("Shrinking... {} bytes   ", render(&lines).len()).green()
+                );
+                let _ = std::io::stdout().flush();
+            } else {
+                from += chunk;
+            }
+        }
+        chunk /= 2;
+    }
+    lines
+}
+
+pub fn shrink_input(input: &str, mut is_failing: impl FnMut(&str) -> bool) -> String {
+    let mut lines = parse_lines(input);
+    let layout = detect_layout(&lines);
+
+    lines = shrink_chunks(
+        lines,
+        |lines| item_count(lines, layout),
+        |lines, from, to| remove_items(lines, layout, from, to),
+        &mut is_failing,
+    );
+
+    // 形式が分からない場合は, 各行のトークンも減らす
+    if layout == Layout::Plain {
+        for line in 0..lines.len() {
+            lines = shrink_chunks(
+                lines,
+                |lines| lines[line].len(),
+                |lines, from, to| remove_tokens(lines, line, from, to),
+                &mut is_failing,
+            );
+        }
+    }
+    println!();
+
+    render(&lines)
+}
+
+fn shrunk_path(input_path: &Path) -> PathBuf {
+    let stem = input_path.file_stem().unwrap_or_default().to_string_lossy();
+    input_path.with_file_name(format!("{}_min.in", stem))
+}
+
+pub fn shrink_file(
+    problem_str_info: &ProblemStrInfo,
+    config_str_map: &ConfigStrMap,
+    config_map: &ConfigMap,
+    input_path: &Path,
+    time_limit_ms: Option<u64>,
+    eps: Option<f64>,
+) -> Result<()> {
+    let mut execute_config: HashMap<String, String> = HashMap::new();
+    execute_config.extend(config_str_map.iter().map(|(k, v)| (k.clone(), v.clone())));
+    execute_config.extend(problem_str_info.iter().map(|(k, v)| (k.clone(), v.clone())));
+    let execute_command = make_execute_command(execute_config.clone())?;
+    let brute_command = make_brute_command(execute_config)?;
+
+    if let ConfigValue::Boolean(true) = config_map
+        .get("need_to_compile")
+        .unwrap_or(&ConfigValue::Boolean(false))
+    {
+        if compile(problem_str_info, config_str_map)? != Status::AC {
+            return Ok(());
+        }
+    }
+
+    let time_limit = get_time_limit(time_limit_ms, config_map);
+    let input = fs::read_to_string(input_path)
+        .with_context(|| format!("Failed to read {}", input_path.display()))?;
+    println!("{}", format!("{:-^30}", " Shrink ").blue());
+    println!("{}: {}", "Execute command".green(), execute_command);
+    println!("{}: {}", "Brute command".green(), brute_command);
+    compare_with_brute(&execute_command, &brute_command, &input, time_limit, eps)?
+        .context("The given input does not fail")?;
+
+    let shrunk = shrink_input(&input, |candidate| {
+        compare_with_brute(&execute_command, &brute_command, candidate, time_limit, eps)
+            .is_ok_and(|mismatch| mismatch.is_some())
+    });
+    let mismatch = compare_with_brute(&execute_command, &brute_command, &shrunk, time_limit, eps)?
+        .context("Shrunk input no longer fails")?;
+
+    let output_path = shrunk_path(input_path);
+    fs::write(&output_path, &shrunk)?;
+    fs::write(output_path.with_extension("out"), &mismatch.expected)?;
+    println!(
+        "{} {} -> {} bytes",
+        "Shrunk".green(),
+        input.len(),
+        shrunk.len()
+    );
+    println!("{} {}", "Saved to".green(), output_path.display());
+    println!("{}", shrunk);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_detect_layout() {
+        let lines = parse_lines("3\n1 2 3\n");
+        assert_eq!(Layout::Array { count_token: 0 }, detect_layout(&lines));
+
+        let lines = parse_lines("3 10\n1 2\n3 4\n5 6\n");
+        assert_eq!(Layout::Rows { count_token: 0 }, detect_layout(&lines));
+
+        let lines = parse_lines("5 2\n1 2\n3 4\n");
+        assert_eq!(Layout::Rows { count_token: 1 }, detect_layout(&lines));
+
+        let lines = parse_lines("abc\nde\n");
+        assert_eq!(Layout::Plain, detect_layout(&lines));
+    }
+
+    #[test]
+    fn test_shrink_input() {
+        // 7を含んでいれば失敗する
+        let input = "8\n1 5 2 7 3 9 4 6\n";
+        let shrunk = shrink_input(input, |candidate| {
+            let lines = parse_lines(candidate);
+            lines.len() == 2
+                && lines[0][0] == lines[1].len().to_string()
+                && lines[1].contains(&"7".to_string())
+        });
+        assert_eq!("1\n7\n", shrunk);
+
+        let input = "4\n1 1\n2 2\n7 7\n3 3\n";
+        let shrunk = shrink_input(input, |candidate| candidate.contains('7'));
+        assert_eq!("1\n7 7\n", shrunk);
+
+        let input = "abc\nx 7 y z\n";
+        let shrunk = shrink_input(input, |candidate| candidate.contains('7'));
+        assert_eq!("7\n", shrunk);
+    }
+}
@@ -0,0 +1,208 @@
+use anyhow::{Context, Result};
+use colored::*;
+use prettytable::{format, Cell, Row, Table};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::ac_scraper::{get_user_screen_name, get_with_session};
+use crate::config::{ConfigMap, ConfigValue, ProblemStrInfo};
+use crate::data::ACN;
+use crate::util::str_format;
+
+const STANDINGS_URL: &str = "https://atcoder.jp/contests/{{contest}}/standings/json";
+// 順位表の得点は100倍, 経過時間はナノ秒で返ってくる
+const SCORE_SCALE: i64 = 100;
+const NANOS_PER_SEC: i64 = 1_000_000_000;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Standings {
+    task_info: Vec<TaskInfo>,
+    standings_data: Vec<StandingsRow>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct TaskInfo {
+    assignment: String,
+    task_screen_name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct StandingsRow {
+    rank: i64,
+    user_screen_name: String,
+    total_result: TaskResult,
+    task_results: HashMap<String, TaskResult>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct TaskResult {
+    score: i64,
+    elapsed: i64,
+    penalty: i64,
+}
+
+fn format_score(score: i64) -> String {
+    if score % SCORE_SCALE == 0 {
+        (score / SCORE_SCALE).to_string()
+    } else {
+        format!("{:.2}", score as f64 / SCORE_SCALE as f64)
+    }
+}
+
+fn format_elapsed(elapsed: i64) -> String {
+    let secs = elapsed / NANOS_PER_SEC;
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+fn format_time(result: &TaskResult) -> String {
+    if result.penalty > 0 {
+        format!("{} (+{})", format_elapsed(result.elapsed), result.penalty)
+    } else {
+        format_elapsed(result.elapsed)
+    }
+}
+
+// 解けた問題は得点と時間, 解けていない問題は誤答数だけを表示する
+fn format_task_result(result: Option<&TaskResult>) -> ColoredString {
+    match result {
+        Some(result) if result.score > 0 => {
+            format!("{} {}", format_score(result.score), format_time(result)).green()
+        }
+        Some(result) if result.penalty > 0 => format!("(-{})", result.penalty).red(),
+        _ => "".normal(),
+    }
+}
+
+fn get_friends(config_map: &ConfigMap) -> Vec<String> {
+    match config_map.get("friends") {
+        Some(ConfigValue::Vector(friends)) => friends
+            .iter()
+            .filter_map(|friend| match friend {
+                ConfigValue::String(friend) => Some(friend.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn print_standings(standings: &Standings, me: &str, users: &[String]) {
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_BOX_CHARS);
+    let mut titles: Vec<Cell> = ["Rank", "User", "Score", "Time"]
+        .iter()
+        .map(|title| Cell::new(title))
+        .collect();
+    titles.extend(
+        standings
+            .task_info
+            .iter()
+            .map(|task| Cell::new(&task.assignment)),
+    );
+    table.set_titles(Row::new(titles));
+
+    for row in standings.standings_data.iter().filter(|row| {
+        users
+            .iter()
+            .any(|u| u.eq_ignore_ascii_case(&row.user_screen_name))
+    }) {
+        let user = if row.user_screen_name.eq_ignore_ascii_case(me) {
+            row.user_screen_name.bold().to_string()
+        } else {
+            row.user_screen_name.clone()
+        };
+        let mut cells = vec![
+            Cell::new(&row.rank.to_string()).style_spec("r"),
+            Cell::new(&user),
+            Cell::new(&format_score(row.total_result.score)).style_spec("r"),
+            Cell::new(&format_time(&row.total_result)),
+        ];
+        cells.extend(standings.task_info.iter().map(|task| {
+            Cell::new(&format_task_result(row.task_results.get(&task.task_screen_name)).to_string())
+        }));
+        table.add_row(Row::new(cells));
+    }
+    table.printstd();
+}
+
+pub async fn show_standings(acn: &ACN, contest_str_info: &ProblemStrInfo) -> Result<()> {
+    let me = match acn.config_str_map.get("username") {
+        Some(username) => username.clone(),
+        None => get_user_screen_name(acn).await?,
+    };
+    let mut users = vec![me.clone()];
+    users.extend(get_friends(&acn.config_map));
+
+    let url = str_format(STANDINGS_URL.to_string(), contest_str_info);
+    let body = get_with_session(acn, &url).await?.text().await?;
+    let standings: Standings =
+        serde_json::from_str(&body).with_context(|| format!("Failed to parse {}", url))?;
+
+    println!("{}", format!("{:-^30}", " Standings ").blue());
+    print_standings(&standings, &me, &users);
+    let missing: Vec<&String> = users
+        .iter()
+        .filter(|user| {
+            !standings
+                .standings_data
+                .iter()
+                .any(|row| row.user_screen_name.eq_ignore_ascii_case(user))
+        })
+        .collect();
+    for user in missing {
+        println!("{} {}", "Not in the standings:".yellow(), user);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_standings() {
+        let body = r#"{
+            "TaskInfo": [
+                {"Assignment": "A", "TaskName": "Past ABCs", "TaskScreenName": "abc350_a"},
+                {"Assignment": "B", "TaskName": "Dentist Aoki", "TaskScreenName": "abc350_b"}
+            ],
+            "StandingsData": [{
+                "Rank": 12,
+                "UserScreenName": "alice",
+                "TotalResult": {"Score": 30000, "Elapsed": 754000000000, "Penalty": 1, "Count": 3},
+                "TaskResults": {
+                    "abc350_a": {"Score": 10000, "Elapsed": 65000000000, "Penalty": 0, "Count": 1},
+                    "abc350_b": {"Score": 0, "Elapsed": 0, "Penalty": 2, "Count": 2}
+                }
+            }]
+        }"#;
+        let standings: Standings = serde_json::from_str(body).unwrap();
+        assert_eq!(2, standings.task_info.len());
+        let row = &standings.standings_data[0];
+        assert_eq!(12, row.rank);
+        assert_eq!("300", format_score(row.total_result.score));
+        assert_eq!("12:34 (+1)", format_time(&row.total_result));
+        assert_eq!(
+            "100 01:05",
+            format_task_result(row.task_results.get("abc350_a"))
+                .clear()
+                .to_string()
+        );
+        assert_eq!(
+            "(-2)",
+            format_task_result(row.task_results.get("abc350_b"))
+                .clear()
+                .to_string()
+        );
+        assert_eq!("", format_task_result(None).to_string());
+    }
+}
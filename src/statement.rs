@@ -0,0 +1,381 @@
+use anyhow::{anyhow, Result};
+use colored::*;
+use ego_tree::NodeRef;
+use regex::Regex;
+use scraper::{ElementRef, Html, Node, Selector};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::ac_scraper::{get_language, Samples};
+use crate::config::ConfigStrMap;
+
+const DEFAULT_PAGER: &str = "less -R";
+const PRE_INDENT: &str = "    ";
+
+// --sectionで使える名前と, 対応する見出し
+const SECTION_ALIASES: [(&str, &[&str]); 5] = [
+    ("statement", &["問題文", "Problem Statement"]),
+    ("constraints", &["制約", "Constraints"]),
+    ("input", &["入力", "Input"]),
+    ("output", &["出力", "Output"]),
+    (
+        "samples",
+        &["入力例", "出力例", "Sample Input", "Sample Output"],
+    ),
+];
+
+// "入力"と"入力例 1"を区別するため, 見出しは完全一致か番号付きの形で比べる
+fn matches_section(title: &str, filter: &str) -> bool {
+    let title = title.trim();
+    match SECTION_ALIASES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(filter))
+    {
+        Some((_, headings)) => headings
+            .iter()
+            .any(|heading| title == *heading || title.starts_with(&format!("{} ", heading))),
+        None => title.to_lowercase().contains(&filter.to_lowercase()),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatementSection {
+    pub title: String,
+    pub body: String,
+}
+
+// AtCoderの問題文で使われる基本的なLaTeXを読める文字に置き換える
+fn latex_to_text(latex: &str) -> String {
+    const SYMBOLS: [(&str, &str); 26] = [
+        ("\\leq", "≤"),
+        ("\\geq", "≥"),
+        ("\\le", "≤"),
+        ("\\ge", "≥"),
+        ("\\neq", "≠"),
+        ("\\ne", "≠"),
+        ("\\lt", "<"),
+        ("\\gt", ">"),
+        ("\\times", "×"),
+        ("\\ldots", "..."),
+        ("\\cdots", "..."),
+        ("\\dots", "..."),
+        ("\\vdots", "⋮"),
+        ("\\cdot", "·"),
+        ("\\pm", "±"),
+        ("\\infty", "∞"),
+        ("\\sum", "Σ"),
+        ("\\prod", "Π"),
+        ("\\bmod", "mod"),
+        ("\\left", ""),
+        ("\\right", ""),
+        ("\\{", "{"),
+        ("\\}", "}"),
+        ("\\,", " "),
+        ("\\ ", " "),
+        ("~", " "),
+    ];
+    let mut text = latex.to_string();
+    for (from, to) in SYMBOLS {
+        // \leが\leftの一部に一致しないよう, 英字のコマンドは単語の区切りまでを見る
+        let boundary = if from.ends_with(char::is_alphabetic) {
+            r"\b"
+        } else {
+            ""
+        };
+        let re = Regex::new(&format!("{}{}", regex::escape(from), boundary)).unwrap();
+        text = re.replace_all(&text, to).to_string();
+    }
+
+    let frac = Regex::new(r"\\[dt]?frac\{([^{}]*)\}\{([^{}]*)\}").unwrap();
+    let script = Regex::new(r"([\^_])\{([^{}]*)\}").unwrap();
+    let command_arg =
+        Regex::new(r"\\(?:mathrm|text|mathbf|mathit|operatorname)\{([^{}]*)\}").unwrap();
+    loop {
+        let next = frac.replace_all(&text, |caps: &regex::Captures| {
+            let wrap = |s: &str| {
+                if s.chars().all(|c| c.is_alphanumeric()) {
+                    s.to_string()
+                } else {
+                    format!("({})", s)
+                }
+            };
+            format!("{}/{}", wrap(&caps[1]), wrap(&caps[2]))
+        });
+        let next = script.replace_all(&next, |caps: &regex::Captures| {
+            if caps[2].chars().count() == 1 {
+                format!("{}{}", &caps[1], &caps[2])
+            } else {
+                format!("{}({})", &caps[1], &caps[2])
+            }
+        });
+        let next = command_arg.replace_all(&next, "$1").to_string();
+        if next == text {
+            break;
+        }
+        text = next;
+    }
+
+    // 残ったコマンドは名前だけにする(\max -> max)
+    let command = Regex::new(r"\\([A-Za-z]+)").unwrap();
+    command.replace_all(&text, "$1").to_string()
+}
+
+fn push_text(out: &mut String, text: &str) {
+    let collapsed = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+    let leading = text.starts_with(char::is_whitespace);
+    let trailing = text.ends_with(char::is_whitespace);
+    if collapsed.is_empty() {
+        if leading && !out.is_empty() && !out.ends_with([' ', '\n']) {
+            out.push(' ');
+        }
+        return;
+    }
+    if leading && !out.is_empty() && !out.ends_with([' ', '\n']) {
+        out.push(' ');
+    }
+    out.push_str(&collapsed);
+    if trailing {
+        out.push(' ');
+    }
+}
+
+fn end_line(out: &mut String) {
+    while out.ends_with(' ') {
+        out.pop();
+    }
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+fn end_paragraph(out: &mut String) {
+    end_line(out);
+    if !out.is_empty() && !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+fn render_children(node: NodeRef<Node>, out: &mut String, in_pre: bool) {
+    for child in node.children() {
+        render_node(child, out, in_pre);
+    }
+}
+
+fn render_node(node: NodeRef<Node>, out: &mut String, in_pre: bool) {
+    match node.value() {
+        Node::Text(text) if in_pre => out.push_str(text),
+        Node::Text(text) => push_text(out, text),
+        Node::Element(element) => match element.name() {
+            "h3" | "script" | "style" | "button" => {}
+            "var" => {
+                let latex = ElementRef::wrap(node)
+                    .map(|e| e.text().collect::<String>())
+                    .unwrap_or_default();
+                let text = latex_to_text(&latex);
+                if in_pre {
+                    out.push_str(&text);
+                } else {
+                    push_text(out, &text);
+                }
+            }
+            "br" => {
+                end_line(out);
+            }
+            "pre" => {
+                end_paragraph(out);
+                let mut pre = String::new();
+                render_children(node, &mut pre, true);
+                for line in pre.trim_end_matches('\n').lines() {
+                    out.push_str(&format!("{}{}\n", PRE_INDENT, line));
+                }
+                out.push('\n');
+            }
+            "li" => {
+                end_line(out);
+                out.push_str("- ");
+                render_children(node, out, in_pre);
+                end_line(out);
+            }
+            "p" | "div" | "ul" | "ol" | "section" | "blockquote" => {
+                end_paragraph(out);
+                render_children(node, out, in_pre);
+                end_paragraph(out);
+            }
+            _ => render_children(node, out, in_pre),
+        },
+        _ => {}
+    }
+}
+
+fn render_element(element: ElementRef) -> String {
+    let mut out = String::new();
+    render_children(*element, &mut out, false);
+    out.trim_start_matches('\n').trim_end().to_string()
+}
+
+fn parse_sections(root: ElementRef) -> Vec<StatementSection> {
+    let section_selector = Selector::parse("section").unwrap();
+    let h3_selector = Selector::parse("h3").unwrap();
+    root.select(&section_selector)
+        .filter_map(|section| {
+            let title = section
+                .select(&h3_selector)
+                .next()?
+                .text()
+                .collect::<String>()
+                .trim()
+                .to_string();
+            Some(StatementSection {
+                title,
+                body: render_element(section),
+            })
+        })
+        .collect()
+}
+
+// 問題文は日英両方を含むことがあるので, 言語ごとに見出しと本文の組にする
+pub fn parse_statements(doc: &Html) -> HashMap<String, Vec<StatementSection>> {
+    let mut statements: HashMap<String, Vec<StatementSection>> = HashMap::new();
+    for language in ["ja", "en"] {
+        let selector = Selector::parse(&format!("#task-statement .lang-{}", language)).unwrap();
+        if let Some(root) = doc.select(&selector).next() {
+            statements.insert(language.to_string(), parse_sections(root));
+        }
+    }
+    if statements.is_empty() {
+        let selector = Selector::parse("#task-statement").unwrap();
+        if let Some(root) = doc.select(&selector).next() {
+            statements.insert("ja".to_string(), parse_sections(root));
+        }
+    }
+    statements
+}
+
+fn terminal_height() -> Option<usize> {
+    unsafe {
+        if libc::isatty(libc::STDOUT_FILENO) == 0 {
+            return None;
+        }
+        let mut winsize: libc::winsize = std::mem::zeroed();
+        if libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut winsize) != 0 {
+            return None;
+        }
+        Some(winsize.ws_row as usize)
+    }
+}
+
+// 端末に収まらない場合は$PAGERで表示する
+fn print_paged(text: &str) -> Result<()> {
+    let fits = terminal_height().is_none_or(|height| text.lines().count() < height);
+    if fits {
+        print!("{}", text);
+        return Ok(());
+    }
+    let pager = env::var("PAGER").unwrap_or_else(|_| DEFAULT_PAGER.to_string());
+    let mut parts = pager.split_whitespace();
+    let Some(command) = parts.next() else {
+        print!("{}", text);
+        return Ok(());
+    };
+    let Ok(mut child) = Command::new(command)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn()
+    else {
+        print!("{}", text);
+        return Ok(());
+    };
+    // ページャーを途中で閉じた場合の書き込みエラーは無視する
+    let _ = child.stdin.take().unwrap().write_all(text.as_bytes());
+    child.wait()?;
+    Ok(())
+}
+
+pub fn show_statement(
+    samples: &Samples,
+    config_str_map: &ConfigStrMap,
+    section: &Option<String>,
+) -> Result<()> {
+    let language = get_language(config_str_map)?;
+    let sections = samples
+        .statements
+        .get(language)
+        .or_else(|| samples.statements.values().next())
+        .ok_or_else(|| anyhow!("Couldn't find the problem statement"))?;
+
+    let mut text = String::new();
+    for s in sections.iter().filter(|s| {
+        section
+            .as_ref()
+            .is_none_or(|f| matches_section(&s.title, f))
+    }) {
+        text.push_str(&format!("{}\n{}\n\n", s.title.blue().bold(), s.body));
+    }
+    if text.is_empty() {
+        let titles: Vec<&str> = sections.iter().map(|s| s.title.as_str()).collect();
+        return Err(anyhow!(
+            "Couldn't find the section {} (available: {})",
+            section.as_deref().unwrap_or_default(),
+            titles.join(", ")
+        ));
+    }
+    print_paged(&text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_latex_to_text() {
+        assert_eq!("1 ≤ N ≤ 10^5", latex_to_text("1 \\leq N \\leq 10^5"));
+        assert_eq!("2^(N-1)", latex_to_text("2^{N-1}"));
+        assert_eq!(
+            "A_1, A_2, ..., A_N",
+            latex_to_text("A_1, A_2, \\ldots, A_N")
+        );
+        assert_eq!("(N+1)/2", latex_to_text("\\frac{N+1}{2}"));
+        assert_eq!("max(A_i)", latex_to_text("\\max(A_i)"));
+        assert_eq!("998244353", latex_to_text("\\mathrm{998244353}"));
+    }
+
+    #[test]
+    fn test_parse_statements() {
+        let html = r#"<div id="task-statement"><span class="lang">
+            <span class="lang-ja">
+                <div class="part"><section><h3>問題文</h3>
+                    <p>長さ <var>N</var> の数列が与えられます.</p>
+                </section></div>
+                <div class="part"><section><h3>制約</h3>
+                    <ul><li><var>1 \leq N \leq 100</var></li><li>入力は全て整数</li></ul>
+                </section></div>
+                <div class="io-style"><div class="part"><section><h3>入力</h3>
+                    <pre><var>N</var>
+<var>A_1</var> <var>\ldots</var> <var>A_N</var>
+</pre>
+                </section></div></div>
+            </span>
+            <span class="lang-en">
+                <div class="part"><section><h3>Constraints</h3>
+                    <ul><li><var>1 \leq N \leq 100</var></li></ul>
+                </section></div>
+            </span>
+        </span></div>"#;
+        let statements = parse_statements(&Html::parse_document(html));
+
+        let ja = &statements["ja"];
+        assert_eq!(3, ja.len());
+        assert_eq!("問題文", ja[0].title);
+        assert_eq!("長さ N の数列が与えられます.", ja[0].body);
+        assert_eq!("- 1 ≤ N ≤ 100\n- 入力は全て整数", ja[1].body);
+        assert_eq!("    N\n    A_1 ... A_N", ja[2].body);
+
+        let en = &statements["en"];
+        assert_eq!(1, en.len());
+        assert!(matches_section(&en[0].title, "constraints"));
+        assert!(!matches_section(&ja[2].title, "samples"));
+    }
+}
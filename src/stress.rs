@@ -0,0 +1,240 @@
+use anyhow::{anyhow, Context, Result};
+use colored::*;
+use std::collections::HashMap;
+use std::io::Write;
+use std::time::Duration;
+
+use crate::check_samples::{
+    compile, execute_with_time_limit, get_runtime_error, get_time_limit, is_ac, ExecutionResult,
+    FailedDetail, Status,
+};
+use crate::config::{
+    make_brute_command, make_execute_command, make_generator_command, ConfigMap, ConfigStrMap,
+    ConfigValue, ProblemStrInfo,
+};
+use crate::custom_cases::{get_test_dir, save_custom_case};
+use crate::diff::display_failed_diff;
+use crate::shrink::shrink_input;
+use crate::util::split_one_line_command;
+
+// 生成器と愚直解は遅くてもよいので長めに待つ
+const HELPER_TIME_LIMIT: Duration = Duration::from_secs(60);
+const COUNTEREXAMPLE_PREFIX: &str = "stress";
+
+pub fn run_command(
+    command_line: &str,
+    input: String,
+    time_limit: Duration,
+) -> Result<ExecutionResult> {
+    let (command, args) = split_one_line_command(command_line);
+    execute_with_time_limit(command, &args, input, time_limit)
+}
+
+// 生成器や愚直解が失敗した場合はストレステストを続けられない
+fn check_helper(name: &str, result: &ExecutionResult) -> Result<()> {
+    if result.timed_out {
+        return Err(anyhow!("{} timed out", name));
+    }
+    if let Some(runtime_error) = result.exit_status.as_ref().and_then(get_runtime_error) {
+        return Err(anyhow!(
+            "{} failed: {}\n{}",
+            name,
+            runtime_error,
+            result.stderr
+        ));
+    }
+    Ok(())
+}
+
+pub struct Mismatch {
+    pub status: Status,
+    pub message: Option<String>,
+    pub expected: String,
+    pub output: String,
+    pub stderr: String,
+}
+
+// 愚直解と出力を比べる. 一致すればNone
+pub fn compare_with_brute(
+    execute_command: &str,
+    brute_command: &str,
+    input: &str,
+    time_limit: Duration,
+    eps: Option<f64>,
+) -> Result<Option<Mismatch>> {
+    let brute = run_command(brute_command, input.to_string(), HELPER_TIME_LIMIT)?;
+    check_helper("Brute force solution", &brute)?;
+    let result = run_command(execute_command, input.to_string(), time_limit)?;
+
+    let message = result.exit_status.as_ref().and_then(get_runtime_error);
+    let status = if result.timed_out {
+        Status::TLE
+    } else if message.is_some() {
+        Status::RE
+    } else if is_ac(&brute.stdout, &result.stdout, eps) {
+        return Ok(None);
+    } else {
+        Status::WA
+    };
+    Ok(Some(Mismatch {
+        status,
+        message,
+        expected: brute.stdout,
+        output: result.stdout,
+        stderr: result.stderr,
+    }))
+}
+
+pub fn stress_test(
+    problem_str_info: &ProblemStrInfo,
+    config_str_map: &ConfigStrMap,
+    config_map: &ConfigMap,
+    time_limit_ms: Option<u64>,
+    count: usize,
+    eps: Option<f64>,
+    shrink: bool,
+) -> Result<Status> {
+    let mut execute_config: HashMap<String, String> = HashMap::new();
+    execute_config.extend(config_str_map.iter().map(|(k, v)| (k.clone(), v.clone())));
+    execute_config.extend(problem_str_info.iter().map(|(k, v)| (k.clone(), v.clone())));
+    let execute_command = make_execute_command(execute_config.clone())?;
+    let brute_command = make_brute_command(execute_config.clone())?;
+    // {{seed}}は毎回変わるので, 生成器のコマンドはループ内で作る
+    make_generator_command(execute_config.clone())?;
+
+    if let ConfigValue::Boolean(true) = config_map
+        .get("need_to_compile")
+        .unwrap_or(&ConfigValue::Boolean(false))
+    {
+        if compile(problem_str_info, config_str_map)? != Status::AC {
+            return Ok(Status::CE);
+        }
+    }
+
+    let time_limit = get_time_limit(time_limit_ms, config_map);
+    println!("{}", format!("{:-^30}", " Stress ").blue());
+    println!("{}: {}", "Execute command".green(), execute_command);
+    println!("{}: {}", "Brute command".green(), brute_command);
+    println!(
+        "{}: {}",
+        "Generator command".green(),
+        execute_config["generator_command"]
+    );
+    println!("{}: {} ms", "Time limit".green(), time_limit.as_millis());
+
+    for seed in 1..=count {
+        print!("\r{}", format!("Test {}/{}", seed, count).green());
+        std::io::stdout().flush()?;
+
+        let mut generator_config = execute_config.clone();
+        generator_config.insert("seed".to_string(), seed.to_string());
+        let generator_command = make_generator_command(generator_config)?;
+        let generated = run_command(&generator_command, "".to_string(), HELPER_TIME_LIMIT)?;
+        check_helper("Generator", &generated)?;
+        let input = generated.stdout;
+
+        let Some(mismatch) =
+            compare_with_brute(&execute_command, &brute_command, &input, time_limit, eps)?
+        else {
+            continue;
+        };
+
+        println!();
+        println!(
+            "{} {}",
+            format!("Found a counterexample with seed {}:", seed).yellow(),
+            mismatch.status.as_display_string()
+        );
+        let (input, mismatch) = if shrink {
+            let input = shrink_input(&input, |candidate| {
+                compare_with_brute(&execute_command, &brute_command, candidate, time_limit, eps)
+                    .is_ok_and(|mismatch| mismatch.is_some())
+            });
+            let mismatch =
+                compare_with_brute(&execute_command, &brute_command, &input, time_limit, eps)?
+                    .context("Shrunk input no longer fails")?;
+            (input, mismatch)
+        } else {
+            (input, mismatch)
+        };
+        if let Some(test_dir) = get_test_dir(problem_str_info, config_str_map)? {
+            let path =
+                save_custom_case(&test_dir, COUNTEREXAMPLE_PREFIX, &input, &mismatch.expected)?;
+            println!("{} {}", "Saved to".green(), path.display());
+        } else {
+            println!(
+                "{}",
+                "Set test_dir in your config file to save counterexamples.".yellow()
+            );
+        }
+        let status = mismatch.status;
+        display_failed_diff(
+            vec![FailedDetail {
+                name: format!("seed {}", seed),
+                status,
+                message: mismatch.message,
+                input,
+                expected: mismatch.expected,
+                output: mismatch.output,
+                stderr: mismatch.stderr,
+            }],
+            eps,
+        );
+        return Ok(status);
+    }
+
+    println!();
+    println!(
+        "{}",
+        format!("No counterexample found in {} tests.", count).green()
+    );
+    Ok(Status::AC)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::TempDir;
+    use std::fs;
+
+    #[test]
+    fn test_stress_test() {
+        let work_dir = TempDir::new("stress").unwrap();
+        work_dir.write("gen.sh", "echo $1\n");
+        work_dir.write("brute.sh", "read x\necho $((x * 2))\n");
+        work_dir.write(
+            "main.sh",
+            "read x\nif [ $x = 3 ]; then echo 0; else echo $((x + x)); fi\n",
+        );
+
+        let dir = work_dir.path().to_str().unwrap();
+        let mut config_str_map = ConfigStrMap::new();
+        config_str_map.insert("execute_command".to_string(), format!("sh {}/main.sh", dir));
+        config_str_map.insert("brute_command".to_string(), format!("sh {}/brute.sh", dir));
+        config_str_map.insert(
+            "generator_command".to_string(),
+            format!("sh {}/gen.sh {{{{seed}}}}", dir),
+        );
+        config_str_map.insert("test_dir".to_string(), format!("{}/tests", dir));
+
+        let status = stress_test(
+            &ProblemStrInfo::new(),
+            &config_str_map,
+            &ConfigMap::new(),
+            None,
+            10,
+            None,
+            false,
+        )
+        .unwrap();
+        assert!(status == Status::WA);
+        assert_eq!(
+            "3\n",
+            fs::read_to_string(work_dir.path().join("tests/stress1.in")).unwrap()
+        );
+        assert_eq!(
+            "6\n",
+            fs::read_to_string(work_dir.path().join("tests/stress1.out")).unwrap()
+        );
+    }
+}
@@ -0,0 +1,515 @@
+use anyhow::{Context, Result};
+use colored::*;
+use prettytable::{format, row, Table};
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+use crate::ac_scraper::{get_task_list, get_with_session};
+use crate::cache::{load, save, sync_state_path};
+use crate::config::ProblemStrInfo;
+use crate::data::ACN;
+use crate::prepare::resolve_path;
+use crate::util::{cell_text, str_format, CLEAR_SCREEN};
+
+const SUBMISSIONS_URL: &str = "https://atcoder.jp/contests/{{contest}}/submissions/me";
+const SUBMISSION_URL: &str =
+    "https://atcoder.jp/contests/{{contest}}/submissions/{{submission_id}}";
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+// 採点が止まっている場合に待ち続けないようにする
+const MAX_POLL_DURATION: Duration = Duration::from_secs(600);
+// 提出ページを続けて取得するときの間隔
+const FETCH_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub id: u64,
+    pub time: String,
+    pub task: String,
+    pub task_screen_name: String,
+    pub language: String,
+    pub score: String,
+    pub status: String,
+    pub runtime: String,
+    pub memory: String,
+}
+
+impl Submission {
+    // WJ, WRや"3/7 TLE"のような採点中の表示
+    fn is_pending(&self) -> bool {
+        let judging = Regex::new(r"^\d+\s*/\s*\d+").unwrap();
+        self.status.contains("WJ") || self.status.contains("WR") || judging.is_match(&self.status)
+    }
+
+    fn colored_status(&self) -> ColoredString {
+        match self.status.as_str() {
+            "AC" => self.status.green(),
+            "CE" => self.status.magenta(),
+            _ if self.is_pending() => self.status.normal(),
+            _ => self.status.yellow(),
+        }
+    }
+}
+
+// リンク先のパスの最後の部分を取り出す
+fn link_name(row: &ElementRef, selector: &Selector) -> Option<String> {
+    let href = row.select(selector).next()?.value().attr("href")?;
+    Some(href.rsplit('/').next()?.to_string())
+}
+
+// 採点中やCEの行は実行時間とメモリの列が結合されている
+fn parse_submissions(body: &str) -> Vec<Submission> {
+    let doc = Html::parse_document(body);
+    let row_selector = Selector::parse("table tbody tr").unwrap();
+    let cell_selector = Selector::parse("td").unwrap();
+    let task_selector = Selector::parse("a[href*='/tasks/']").unwrap();
+    let detail_selector = Selector::parse("a[href*='/submissions/']").unwrap();
+    let time_zone = Regex::new(r"[+-]\d{4}$").unwrap();
+
+    doc.select(&row_selector)
+        .filter_map(|row| {
+            let cells: Vec<String> = row
+                .select(&cell_selector)
+                .map(|cell| cell_text(&cell))
+                .collect();
+            if cells.len() < 7 {
+                return None;
+            }
+            let has_usage = cells.len() >= 10;
+            Some(Submission {
+                id: link_name(&row, &detail_selector)?.parse().ok()?,
+                time: time_zone.replace(&cells[0], "").to_string(),
+                task: cells[1].clone(),
+                task_screen_name: link_name(&row, &task_selector)?,
+                language: cells[3].clone(),
+                score: cells[4].clone(),
+                status: cells[6].clone(),
+                runtime: if has_usage {
+                    cells[7].clone()
+                } else {
+                    "".into()
+                },
+                memory: if has_usage {
+                    cells[8].clone()
+                } else {
+                    "".into()
+                },
+            })
+        })
+        .collect()
+}
+
+fn print_submissions(submissions: &[Submission]) {
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_BOX_CHARS);
+    table.set_titles(row![
+        "Time", "Task", "Language", "Score", "Status", "Runtime", "Memory"
+    ]);
+    for submission in submissions {
+        table.add_row(row![
+            submission.time,
+            submission.task,
+            submission.language,
+            r->submission.score,
+            submission.colored_status(),
+            r->submission.runtime,
+            r->submission.memory
+        ]);
+    }
+    table.printstd();
+}
+
+// ページの最後の提出がまだ採点中なら, 次のページにも採点中の提出があるので続けて取得する
+async fn fetch_submissions(acn: &ACN, url: &str) -> Result<Vec<Submission>> {
+    let mut submissions: Vec<Submission> = Vec::new();
+    for page in 1.. {
+        let body = get_with_session(acn, &format!("{}page={}", url, page))
+            .await?
+            .text()
+            .await?;
+        let page_submissions = parse_submissions(&body);
+        let has_next = page_submissions.last().is_some_and(|s| s.is_pending())
+            && !page_submissions
+                .iter()
+                .any(|s| submissions.iter().any(|t| t.id == s.id));
+        submissions.extend(page_submissions);
+        if !has_next {
+            break;
+        }
+    }
+    Ok(submissions)
+}
+
+// 採点中の提出がなくなるまで取得し直す
+pub async fn show_submissions(
+    acn: &ACN,
+    contest_str_info: &ProblemStrInfo,
+    task_screen_name: Option<&str>,
+) -> Result<()> {
+    let mut url = format!(
+        "{}?",
+        str_format(SUBMISSIONS_URL.to_string(), contest_str_info)
+    );
+    if let Some(task_screen_name) = task_screen_name {
+        url = format!("{}f.Task={}&", url, task_screen_name);
+    }
+
+    let start = Instant::now();
+    loop {
+        let submissions = fetch_submissions(acn, &url).await?;
+        print!("{}", CLEAR_SCREEN);
+        println!("{}", format!("{:-^30}", " Submissions ").blue());
+        if submissions.is_empty() {
+            println!("{}", "No submissions yet.".yellow());
+            return Ok(());
+        }
+        print_submissions(&submissions);
+        if !submissions.iter().any(|s| s.is_pending()) {
+            return Ok(());
+        }
+        if start.elapsed() >= MAX_POLL_DURATION {
+            println!(
+                "{}",
+                format!(
+                    "Judge is still running after {} seconds. Stopped waiting.",
+                    MAX_POLL_DURATION.as_secs()
+                )
+                .yellow()
+            );
+            return Ok(());
+        }
+        println!("{}", "Waiting for judge...".green());
+        sleep(POLL_INTERVAL).await;
+    }
+}
+
+// このIDまでの提出は処理済み
+#[derive(Default, Serialize, Deserialize)]
+struct SyncState {
+    last_submission_id: u64,
+}
+
+fn parse_source(body: &str) -> Option<String> {
+    let doc = Html::parse_document(body);
+    let selector = Selector::parse("#submission-code").unwrap();
+    Some(doc.select(&selector).next()?.text().collect())
+}
+
+async fn fetch_source(acn: &ACN, problem_str_info: &ProblemStrInfo) -> Result<String> {
+    let url = str_format(SUBMISSION_URL.to_string(), problem_str_info);
+    let body = get_with_session(acn, &url).await?.text().await?;
+    parse_source(&body).with_context(|| format!("Couldn't find the source code in {}", url))
+}
+
+// 提出一覧は新しい順なので, 前回保存したIDより古い提出が出てきたところで止める
+async fn collect_new_submissions<F, Fut>(
+    last_submission_id: u64,
+    mut fetch_page: F,
+) -> Result<Vec<Submission>>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<Vec<Submission>>>,
+{
+    let mut new_submissions: Vec<Submission> = Vec::new();
+    for page in 1.. {
+        let submissions = fetch_page(page).await?;
+        let page_size = submissions.len();
+        let fresh: Vec<Submission> = submissions
+            .into_iter()
+            .filter(|s| s.id > last_submission_id && !new_submissions.iter().any(|n| n.id == s.id))
+            .collect();
+        let reached_end = fresh.is_empty() || fresh.len() < page_size;
+        new_submissions.extend(fresh);
+        if reached_end {
+            break;
+        }
+    }
+    Ok(new_submissions)
+}
+
+async fn fetch_new_accepted(
+    acn: &ACN,
+    contest_str_info: &ProblemStrInfo,
+    last_submission_id: u64,
+) -> Result<Vec<Submission>> {
+    let url = str_format(SUBMISSIONS_URL.to_string(), contest_str_info);
+    collect_new_submissions(last_submission_id, |page| {
+        let page_url = format!("{}?f.Status=AC&page={}", url, page);
+        async move {
+            if page > 1 {
+                sleep(FETCH_INTERVAL).await;
+            }
+            let body = get_with_session(acn, &page_url).await?.text().await?;
+            Ok(parse_submissions(&body))
+        }
+    })
+    .await
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum SyncAction {
+    Write(PathBuf),
+    // source_file_pathにすでにある作業中のファイルは上書きしない
+    Exists(PathBuf),
+    // 同じパスに新しいACを書き出すので, 古いACは取得しない
+    Superseded,
+    UnknownTask,
+}
+
+// 新しい提出から順に, それぞれをどう扱うかを決める
+// どの場合も意図して扱ったものなので, カーソルは取得した提出の最新まで進める
+fn plan_sync(
+    submissions: &[Submission],
+    paths: &[Option<PathBuf>],
+    overwrite: bool,
+    exists: impl Fn(&Path) -> bool,
+    last_submission_id: u64,
+) -> (Vec<SyncAction>, u64) {
+    let mut seen: HashSet<&PathBuf> = HashSet::new();
+    let actions = paths
+        .iter()
+        .map(|path| match path {
+            None => SyncAction::UnknownTask,
+            Some(path) if !seen.insert(path) => SyncAction::Superseded,
+            Some(path) if !overwrite && exists(path) => SyncAction::Exists(path.clone()),
+            Some(path) => SyncAction::Write(path.clone()),
+        })
+        .collect();
+    let cursor = submissions
+        .iter()
+        .map(|s| s.id)
+        .fold(last_submission_id, u64::max);
+    (actions, cursor)
+}
+
+// ACした提出のソースを保存する. 2回目以降は前回より新しい提出だけを取得する
+pub async fn sync_submissions(acn: &ACN, contest_str_info: &ProblemStrInfo) -> Result<()> {
+    let state_path = sync_state_path(contest_str_info)?;
+    let state: SyncState = load(&state_path).unwrap_or_default();
+    // archive_pathがなければsource_file_pathに書き出すが, 作業中のファイルは上書きしない
+    let (template, overwrite) = match acn.config_str_map.get("archive_path") {
+        Some(template) => (template, true),
+        None => (&acn.config_str_map["source_file_path"], false),
+    };
+    let labels: HashMap<String, String> = get_task_list(acn, contest_str_info)
+        .await?
+        .into_iter()
+        .map(|task| (task.task_screen_name, task.label.to_lowercase()))
+        .collect();
+
+    let submissions = fetch_new_accepted(acn, contest_str_info, state.last_submission_id).await?;
+    println!("{}", format!("{:-^30}", " Sync submissions ").blue());
+    if submissions.is_empty() {
+        println!("{}", "No new accepted submissions.".green());
+        return Ok(());
+    }
+
+    let mut problem_str_infos: Vec<Option<ProblemStrInfo>> = Vec::new();
+    let mut paths: Vec<Option<PathBuf>> = Vec::new();
+    for submission in &submissions {
+        let Some(label) = labels.get(&submission.task_screen_name) else {
+            problem_str_infos.push(None);
+            paths.push(None);
+            continue;
+        };
+        let mut problem_str_info = contest_str_info.clone();
+        problem_str_info.insert("problem_id".to_string(), label.clone());
+        problem_str_info.insert(
+            "task_screen_name".to_string(),
+            submission.task_screen_name.clone(),
+        );
+        problem_str_info.insert("submission_id".to_string(), submission.id.to_string());
+        paths.push(Some(resolve_path(template, &problem_str_info, acn)?));
+        problem_str_infos.push(Some(problem_str_info));
+    }
+
+    let (actions, last_submission_id) = plan_sync(
+        &submissions,
+        &paths,
+        overwrite,
+        |path| path.exists(),
+        state.last_submission_id,
+    );
+    for ((submission, problem_str_info), action) in
+        submissions.iter().zip(problem_str_infos).zip(actions)
+    {
+        match action {
+            SyncAction::Write(path) => {
+                sleep(FETCH_INTERVAL).await;
+                let source = fetch_source(acn, &problem_str_info.unwrap()).await?;
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                fs::write(&path, source)?;
+                println!("{} (#{})", path.display(), submission.id);
+            }
+            SyncAction::Exists(path) => {
+                println!("{} already exists", path.display());
+            }
+            SyncAction::Superseded => {}
+            SyncAction::UnknownTask => {
+                println!(
+                    "{} {}",
+                    "Skipped unknown task:".yellow(),
+                    submission.task_screen_name
+                );
+            }
+        }
+    }
+
+    save(&state_path, &SyncState { last_submission_id })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_submissions() {
+        let body = r#"<table><tbody>
+            <tr>
+                <td><time class="fixtime-second">2024-04-20 21:05:12+0900</time></td>
+                <td><a href="/contests/abc350/tasks/abc350_a">A - Past ABCs</a></td>
+                <td><a href="/users/user">user</a></td>
+                <td><a>C++ 20 (gcc 12.2)</a></td>
+                <td class="text-right">100</td>
+                <td class="text-right">250 Byte</td>
+                <td class="text-center"><span class="label label-success">AC</span></td>
+                <td class="text-right">1 ms</td>
+                <td class="text-right">3580 KB</td>
+                <td class="text-center"><a href="/contests/abc350/submissions/52473620">Detail</a></td>
+            </tr>
+            <tr>
+                <td><time class="fixtime-second">2024-04-20 21:10:00+0900</time></td>
+                <td><a href="/contests/abc350/tasks/abc350_b">B - Dentist Aoki</a></td>
+                <td><a>user</a></td>
+                <td><a>Python (CPython 3.11.4)</a></td>
+                <td class="text-right">0</td>
+                <td class="text-right">120 Byte</td>
+                <td class="text-center" colspan="3"><span class="label label-default">3/12 WA</span></td>
+                <td class="text-center"><a href="/contests/abc350/submissions/52473999">Detail</a></td>
+            </tr>
+        </tbody></table>"#;
+        let submissions = parse_submissions(body);
+        assert_eq!(2, submissions.len());
+        assert_eq!(
+            Submission {
+                id: 52473620,
+                time: "2024-04-20 21:05:12".to_string(),
+                task: "A - Past ABCs".to_string(),
+                task_screen_name: "abc350_a".to_string(),
+                language: "C++ 20 (gcc 12.2)".to_string(),
+                score: "100".to_string(),
+                status: "AC".to_string(),
+                runtime: "1 ms".to_string(),
+                memory: "3580 KB".to_string(),
+            },
+            submissions[0]
+        );
+        assert!(!submissions[0].is_pending());
+        assert_eq!(52473999, submissions[1].id);
+        assert_eq!("abc350_b", submissions[1].task_screen_name);
+        assert_eq!("3/12 WA", submissions[1].status);
+        assert_eq!("", submissions[1].runtime);
+        assert!(submissions[1].is_pending());
+    }
+
+    #[test]
+    fn test_parse_source() {
+        let body = r#"<pre id="submission-code" data-ace-mode="cpp">#include &lt;iostream&gt;
+int main() { std::cout &lt;&lt; 1 &lt;&lt; std::endl; }
+</pre>"#;
+        assert_eq!(
+            Some("#include <iostream>\nint main() { std::cout << 1 << std::endl; }\n".to_string()),
+            parse_source(body)
+        );
+        assert_eq!(None, parse_source("<html></html>"));
+    }
+
+    fn accepted(ids: &[u64]) -> Vec<Submission> {
+        ids.iter()
+            .map(|&id| Submission {
+                id,
+                time: "".to_string(),
+                task: "".to_string(),
+                task_screen_name: "abc350_a".to_string(),
+                language: "".to_string(),
+                score: "100".to_string(),
+                status: "AC".to_string(),
+                runtime: "".to_string(),
+                memory: "".to_string(),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_collect_new_submissions() {
+        let pages = [vec![9, 8, 7], vec![6, 5, 4], vec![3, 2, 1]];
+        let fetch_page = |page: usize| {
+            let submissions = pages.get(page - 1).map_or(vec![], |ids| accepted(ids));
+            async move { Ok(submissions) }
+        };
+
+        // 前回のカーソルより古い提出が出てきたページで止まる
+        let ids: Vec<u64> = collect_new_submissions(5, fetch_page)
+            .await
+            .unwrap()
+            .iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(vec![9, 8, 7, 6], ids);
+
+        // 初回は空のページまでたどる
+        let ids: Vec<u64> = collect_new_submissions(0, fetch_page)
+            .await
+            .unwrap()
+            .iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(vec![9, 8, 7, 6, 5, 4, 3, 2, 1], ids);
+
+        // 範囲外のページで同じ内容が返ってきても止まる
+        let ids: Vec<u64> = collect_new_submissions(0, |_| async { Ok(accepted(&[2, 1])) })
+            .await
+            .unwrap()
+            .iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(vec![2, 1], ids);
+    }
+
+    #[test]
+    fn test_plan_sync() {
+        let submissions = accepted(&[9, 8, 7, 6]);
+        let path = |name: &str| Some(PathBuf::from(name));
+        let paths = vec![path("a.cpp"), path("b.cpp"), path("a.cpp"), None];
+
+        // b.cppはすでにあるので飛ばすが, カーソルは最新の提出まで進める
+        let (actions, cursor) = plan_sync(&submissions, &paths, false, |p| p.ends_with("b.cpp"), 5);
+        assert_eq!(
+            vec![
+                SyncAction::Write(PathBuf::from("a.cpp")),
+                SyncAction::Exists(PathBuf::from("b.cpp")),
+                SyncAction::Superseded,
+                SyncAction::UnknownTask,
+            ],
+            actions
+        );
+        assert_eq!(9, cursor);
+
+        // archive_pathなら上書きする
+        let (actions, _) = plan_sync(&submissions, &paths, true, |_| true, 5);
+        assert_eq!(SyncAction::Write(PathBuf::from("b.cpp")), actions[1]);
+
+        let (actions, cursor) = plan_sync(&[], &[], false, |_| false, 5);
+        assert!(actions.is_empty());
+        assert_eq!(5, cursor);
+    }
+}
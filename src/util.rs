@@ -1,5 +1,11 @@
 use regex::Regex;
+use scraper::ElementRef;
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub const CLEAR_SCREEN: &str = "\x1B[2J\x1B[1;1H";
 
 #[macro_export]
 macro_rules! cast {
@@ -12,6 +18,53 @@ macro_rules! cast {
     }};
 }
 
+static TEMP_DIR_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+// 作業用の一時ディレクトリ. 途中でエラーやpanicになってもdropで消える
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new(name: &str) -> std::io::Result<Self> {
+        let path = std::env::temp_dir().join(format!(
+            "ac-ninja-{}-{}-{}",
+            name,
+            std::process::id(),
+            TEMP_DIR_COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&path)?;
+        Ok(TempDir { path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // テスト用のスクリプトなどを書き込んでパスを返す
+    #[cfg(test)]
+    pub fn write(&self, file_name: &str, contents: &str) -> PathBuf {
+        let path = self.path.join(file_name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+// 表のセルの文字列を, 空白をまとめて取り出す
+pub fn cell_text(cell: &ElementRef) -> String {
+    cell.text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
 pub fn split_one_line_command(one_line: &str) -> (&str, Vec<&str>) {
     let mut v: Vec<&str> = one_line.split(' ').collect();
     v.reverse();
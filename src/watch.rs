@@ -0,0 +1,62 @@
+use anyhow::{Context, Result};
+use colored::*;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use crate::util::CLEAR_SCREEN;
+
+// エディタは保存時に複数回書き込むことがあるので, 少し待ってからまとめて扱う
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+fn is_source_changed(event: &Event, source_path: &Path) -> bool {
+    matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+        && event
+            .paths
+            .iter()
+            .any(|path| path.file_name() == source_path.file_name())
+}
+
+fn run_on_change(source_path: &Path, on_change: &mut impl FnMut() -> Result<()>) {
+    print!("{}", CLEAR_SCREEN);
+    if let Err(e) = on_change() {
+        eprintln!("{} {:#}", "Error:".red(), e);
+    }
+    println!(
+        "{}",
+        format!(
+            "Watching {} for changes. Press Ctrl-C to stop.",
+            source_path.display()
+        )
+        .blue()
+    );
+}
+
+// ファイルの置き換えで保存するエディタもあるので, ディレクトリごと監視する
+pub fn watch_source_file(
+    source_path: &Path,
+    mut on_change: impl FnMut() -> Result<()>,
+) -> Result<()> {
+    let watch_dir = source_path
+        .parent()
+        .with_context(|| format!("Invalid source file path: {}", source_path.display()))?;
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher
+        .watch(watch_dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch {}", watch_dir.display()))?;
+
+    run_on_change(source_path, &mut on_change);
+    for event in &rx {
+        if !is_source_changed(&event?, source_path) {
+            continue;
+        }
+        thread::sleep(DEBOUNCE);
+        while rx.try_recv().is_ok() {}
+        run_on_change(source_path, &mut on_change);
+    }
+
+    Ok(())
+}